@@ -0,0 +1,170 @@
+use std::path::Path;
+
+use ebur128::{EbuR128, Mode};
+use serde::{Deserialize, Serialize};
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::decode;
+
+/// Samples at or above this magnitude (about -0.01 dBFS) count as clipped.
+const CLIP_LEVEL: f32 = 0.9989;
+
+pub const DEFAULT_SILENCE_THRESHOLD_DB: f64 = -60.0;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct LoudnessStats {
+    pub integrated_lufs: Option<f64>,
+    pub loudness_range_lu: Option<f64>,
+    pub true_peak_dbtp: Option<f64>,
+    pub sample_peak_dbfs: Option<f64>,
+    pub clipped_samples: u64,
+}
+
+#[derive(Default)]
+pub struct LoudnessMeter {
+    meter: Option<EbuR128>,
+    clipped_samples: u64,
+}
+
+impl LoudnessMeter {
+    pub fn add_block(&mut self, samples: &[f32], channels: usize, sample_rate: u32) {
+        if self.meter.is_none() {
+            let mode = Mode::I | Mode::LRA | Mode::TRUE_PEAK | Mode::SAMPLE_PEAK;
+            self.meter = EbuR128::new(channels as u32, sample_rate, mode).ok();
+        }
+        if let Some(meter) = self.meter.as_mut() {
+            if let Err(e) = meter.add_frames_f32(samples) {
+                log::warn!("Loudness meter rejected block: {:?}", e);
+            }
+        }
+        self.clipped_samples += samples.iter().filter(|s| s.abs() >= CLIP_LEVEL).count() as u64;
+    }
+
+    pub fn finish(&self) -> LoudnessStats {
+        let Some(meter) = self.meter.as_ref() else {
+            return LoudnessStats::default();
+        };
+
+        let max_over_channels = |peak: fn(&EbuR128, u32) -> Result<f64, ebur128::Error>| {
+            (0..meter.channels())
+                .filter_map(|ch| peak(meter, ch).ok())
+                .fold(None, |max: Option<f64>, p| Some(max.map_or(p, |m| m.max(p))))
+        };
+
+        LoudnessStats {
+            integrated_lufs: meter.loudness_global().ok().filter(|l| l.is_finite()),
+            loudness_range_lu: meter.loudness_range().ok().filter(|l| l.is_finite()),
+            true_peak_dbtp: max_over_channels(EbuR128::true_peak).and_then(to_db),
+            sample_peak_dbfs: max_over_channels(EbuR128::sample_peak).and_then(to_db),
+            clipped_samples: self.clipped_samples,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct SilenceStats {
+    pub threshold_db: f64,
+    pub leading_secs: f64,
+    pub trailing_secs: f64,
+}
+
+pub struct SilenceDetector {
+    threshold: f32,
+    threshold_db: f64,
+    sample_rate: u32,
+    frames: u64,
+    first_loud: Option<u64>,
+    last_loud: Option<u64>,
+}
+
+impl SilenceDetector {
+    pub fn new(threshold_db: f64) -> Self {
+        SilenceDetector {
+            threshold: 10f64.powf(threshold_db / 20.0) as f32,
+            threshold_db,
+            sample_rate: 0,
+            frames: 0,
+            first_loud: None,
+            last_loud: None,
+        }
+    }
+
+    pub fn add_block(&mut self, samples: &[f32], channels: usize, sample_rate: u32) {
+        self.sample_rate = sample_rate;
+        for frame in samples.chunks(channels.max(1)) {
+            if frame.iter().any(|s| s.abs() >= self.threshold) {
+                self.first_loud.get_or_insert(self.frames);
+                self.last_loud = Some(self.frames);
+            }
+            self.frames += 1;
+        }
+    }
+
+    pub fn finish(&self) -> SilenceStats {
+        if self.sample_rate == 0 {
+            return SilenceStats {
+                threshold_db: self.threshold_db,
+                ..Default::default()
+            };
+        }
+
+        let rate = self.sample_rate as f64;
+        let leading = self.first_loud.unwrap_or(self.frames);
+        let trailing = self.last_loud.map_or(self.frames, |last| self.frames - last - 1);
+
+        SilenceStats {
+            threshold_db: self.threshold_db,
+            leading_secs: leading as f64 / rate,
+            trailing_secs: trailing as f64 / rate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LevelAnalysis {
+    pub loudness: LoudnessStats,
+    pub silence: SilenceStats,
+}
+
+/// Loudness and edge silence of a file, measured in a single decode pass.
+pub fn analyze_levels(path: &Path, silence_threshold_db: f64) -> Result<LevelAnalysis, String> {
+    let mut meter = LoudnessMeter::default();
+    let mut silence = SilenceDetector::new(silence_threshold_db);
+
+    decode::decode_file(path, |samples, channels, rate| {
+        meter.add_block(samples, channels, rate);
+        silence.add_block(samples, channels, rate);
+    })?;
+
+    Ok(LevelAnalysis {
+        loudness: meter.finish(),
+        silence: silence.finish(),
+    })
+}
+
+/// xxh3-128 of the decoded audio quantized to 16 bits, so copies that differ only in
+/// tags or container layout hash the same.
+pub fn pcm_fingerprint(path: &Path) -> Result<String, String> {
+    let mut hasher = Xxh3::new();
+    let mut bytes = Vec::new();
+
+    let summary = decode::decode_file(path, |samples, _, _| {
+        bytes.clear();
+        for sample in samples {
+            let quantized = (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16;
+            bytes.extend_from_slice(&quantized.to_le_bytes());
+        }
+        hasher.update(&bytes);
+    })?;
+
+    if summary.frames == 0 {
+        return Err("Nenhuma amostra decodificada".to_string());
+    }
+    hasher.update(&summary.sample_rate.to_le_bytes());
+    hasher.update(&(summary.channels as u32).to_le_bytes());
+    Ok(format!("{:032x}", hasher.digest128()))
+}
+
+fn to_db(linear: f64) -> Option<f64> {
+    (linear > 0.0).then(|| 20.0 * linear.log10())
+}
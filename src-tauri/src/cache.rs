@@ -0,0 +1,138 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::analysis;
+use crate::commands::{AudioFileInfo, ScanOptions};
+
+pub const CACHE_FILE: &str = "scan-cache.json";
+
+/// Bump whenever analysis output changes so stale results are not served.
+const CACHE_VERSION: u32 = 2;
+
+/// What a file looked like on disk when it was analyzed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Fingerprint {
+    pub size_bytes: u64,
+    pub modified_ns: u64,
+    pub content_hash: Option<String>,
+}
+
+impl Fingerprint {
+    pub fn read(path: &Path, with_hash: bool) -> io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        let modified_ns = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+
+        Ok(Fingerprint {
+            size_bytes: metadata.len(),
+            modified_ns,
+            content_hash: if with_hash { Some(content_hash(path)?) } else { None },
+        })
+    }
+
+    fn matches(&self, cached: &Fingerprint) -> bool {
+        if self.size_bytes != cached.size_bytes || self.modified_ns != cached.modified_ns {
+            return false;
+        }
+        match &self.content_hash {
+            Some(hash) => cached.content_hash.as_ref() == Some(hash),
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CacheEntry {
+    pub fingerprint: Fingerprint,
+    pub options: ScanOptions,
+    pub info: AudioFileInfo,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ScanCache {
+    version: u32,
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl ScanCache {
+    /// Loads the cache, starting empty if it is missing, unreadable or from another version.
+    pub fn load(path: &Path) -> Self {
+        let cache = fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<ScanCache>(&json).ok())
+            .filter(|cache| cache.version == CACHE_VERSION);
+
+        cache.unwrap_or(ScanCache {
+            version: CACHE_VERSION,
+            entries: HashMap::new(),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| format!("Erro ao salvar cache: {}", e))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The cached entry for `file`, if it is unchanged and was analyzed at least as deeply as requested.
+    pub fn get(&self, file: &Path, fingerprint: &Fingerprint, options: &ScanOptions) -> Option<&CacheEntry> {
+        let entry = self.entries.get(&key(file))?;
+        (fingerprint.matches(&entry.fingerprint) && covers(&entry.options, options)).then_some(entry)
+    }
+
+    pub fn insert(&mut self, file: &Path, entry: CacheEntry) {
+        self.entries.insert(key(file), entry);
+    }
+
+    /// Drops entries whose file is gone. Files the last scan skipped, e.g. through its depth or
+    /// exclude options, still exist and keep their entries.
+    pub fn prune(&mut self, seen: &HashSet<String>) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|file, _| seen.contains(file) || Path::new(file).exists());
+        before - self.entries.len()
+    }
+}
+
+pub fn key(file: &Path) -> String {
+    file.to_string_lossy().into_owned()
+}
+
+/// xxh3-128 of the whole file, hex encoded.
+pub fn content_hash(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Xxh3::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(format!("{:032x}", hasher.digest128()))
+}
+
+fn covers(cached: &ScanOptions, requested: &ScanOptions) -> bool {
+    let threshold = |o: &ScanOptions| o.silence_threshold_db.unwrap_or(analysis::DEFAULT_SILENCE_THRESHOLD_DB);
+
+    (cached.deep_validation || !requested.deep_validation)
+        && (cached.decode_test || !requested.decode_test)
+        && (cached.loudness || !requested.loudness)
+        && (cached.audio_fingerprint || !requested.audio_fingerprint)
+        && (!requested.silence || (cached.silence && threshold(cached) == threshold(requested)))
+}
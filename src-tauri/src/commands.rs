@@ -1,17 +1,34 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io::Write;
-use std::path::Path;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Manager};
 
+use crate::analysis::{self, LoudnessStats, SilenceStats};
+use crate::cache::{self, CacheEntry, Fingerprint, ScanCache};
+use crate::convert::{self, BatchOutputs, Encoder, EncodingOptions, EncodingPreset, LoudnessReport, Monitor, OutputOptions};
+use crate::edit::EditReport;
+use crate::duplicates::{self, DuplicateGroup};
+use crate::rules::{self, RuleInfo, RuleViolation};
+use crate::summary::ScanSummary;
+use crate::toolchain::{self, ToolSource, ToolchainSettings, ToolchainStatus};
+use crate::walk::{self, WalkOptions};
+use crate::{decode, sniff, vorbis, wav};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct AudioFileInfo {
     pub name: String,
     pub path: String,
+    /// Path below the scanned folder with `/` separators, as stored in `AudioEntry.files`.
+    #[serde(default)]
+    pub relative_path: String,
     pub size_bytes: u64,
     pub size_display: String,
+    /// Set by `rules::apply` from the analysis error and the enabled rules.
     pub is_valid_ogg: bool,
     pub is_vorbis: bool,
     pub error: Option<String>,
@@ -19,6 +36,61 @@ pub struct AudioFileInfo {
     pub sample_rate: Option<u32>,
     pub channels: Option<u8>,
     pub format: String,
+    pub detected_format: Option<String>,
+    pub codec: Option<String>,
+    pub bits_per_sample: Option<u16>,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub vendor: Option<String>,
+    pub tags: BTreeMap<String, Vec<String>>,
+    pub loop_points: Option<LoopPoints>,
+    pub total_samples: Option<u64>,
+    pub decode_check: Option<DecodeCheck>,
+    pub loudness: Option<LoudnessStats>,
+    pub silence: Option<SilenceStats>,
+    pub content_hash: Option<String>,
+    pub audio_fingerprint: Option<String>,
+    #[serde(default)]
+    pub violations: Vec<RuleViolation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LoopPoints {
+    pub start_sample: u64,
+    pub end_sample: Option<u64>,
+    pub start_secs: f64,
+    pub end_secs: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DecodeCheck {
+    pub decoded_samples: u64,
+    pub error_count: usize,
+    pub errors: Vec<String>,
+    pub length_matches: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanOptions {
+    pub deep_validation: bool,
+    pub decode_test: bool,
+    pub loudness: bool,
+    pub silence: bool,
+    pub silence_threshold_db: Option<f64>,
+    /// Ignore cached results and re-analyze every file.
+    pub refresh: bool,
+    /// Also compare content hashes, not just size and mtime, before trusting the cache.
+    pub verify_hash: bool,
+    /// Hash file contents and report files that are byte-for-byte copies.
+    pub find_duplicates: bool,
+    /// Also hash the decoded audio, catching copies that differ only in tags or muxing.
+    pub audio_fingerprint: bool,
+    #[serde(flatten)]
+    pub walk: WalkOptions,
+    /// Ids from `list_scan_rules` to skip.
+    pub disabled_rules: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,6 +100,18 @@ pub struct ScanResult {
     pub total_valid: usize,
     pub total_invalid: usize,
     pub total_size: String,
+    pub cancelled: bool,
+    pub duplicates: Vec<DuplicateGroup>,
+    /// Folders or links the walk had to skip, such as symlink loops.
+    pub warnings: Vec<String>,
+    pub summary: ScanSummary,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ScanProgress {
+    pub done: usize,
+    pub total: usize,
+    pub current_file: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -54,12 +138,23 @@ pub struct AudioEntry {
     pub jukebox: Option<JukeboxConfig>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AudioEntryWarning {
+    pub audio_id: String,
+    pub file: String,
+    pub message: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProjectData {
     pub config: ModConfig,
     pub audios: Vec<AudioEntry>,
     pub version: String,
     pub saved_at: String,
+    #[serde(default)]
+    pub disabled_rules: Vec<String>,
+    #[serde(default)]
+    pub encoding_options: EncodingOptions,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -70,13 +165,102 @@ pub struct ExportResult {
     pub files_created: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ConvertResult {
     pub success: bool,
     pub output_path: String,
     pub message: String,
+    /// `None` when the conversion was skipped.
+    pub encoder: Option<Encoder>,
+    /// Before/after loudness when the conversion normalized the audio.
+    pub loudness: Option<LoudnessReport>,
+    pub edit: Option<EditReport>,
+    /// The output already existed and the collision policy was `skip`.
+    pub skipped: bool,
+    /// Where the file replaced by this conversion was copied to.
+    pub backup_path: Option<String>,
+    /// The scanner's analysis of the new file, as `scan_audio_folder` would report it.
+    pub output_info: Option<AudioFileInfo>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConvertJobRequest {
+    /// Chosen by the caller so progress events can be matched to jobs before the batch returns.
+    pub id: String,
+    pub source_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConvertJobStatus {
+    Queued,
+    Converting,
+    Done,
+    Skipped,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConvertProgress {
+    pub job_id: String,
+    pub status: ConvertJobStatus,
+    /// 0.0 to 1.0; stays at 0 while the length of the source is unknown.
+    pub progress: f32,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConvertJobOutcome {
+    pub id: String,
+    pub source_path: String,
+    pub status: ConvertJobStatus,
+    pub result: Option<ConvertResult>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchConvertResult {
+    pub jobs: Vec<ConvertJobOutcome>,
+    pub succeeded: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub cancelled: usize,
+}
+
+
+/// Decoded length may differ from the granule length by at most one Vorbis long block.
+const DECODE_LENGTH_TOLERANCE: u64 = 4096;
+
+/// Rough integrated loudness of the vanilla soundtrack.
+const VANILLA_MUSIC_LUFS: f64 = -18.0;
+
+/// How far above the vanilla level a Music cue may sit before it is flagged.
+const LOUDNESS_WARNING_LU: f64 = 4.0;
+
+/// Edge silence on a looped cue longer than this is an audible gap on every loop.
+const LOOP_GAP_WARNING_SECS: f64 = 0.05;
+
+/// Intro length before LOOPSTART above which a whole-file `Looped` cue is flagged.
+const LONG_INTRO_SECS: f64 = 1.0;
+
+/// Normalizes an `AudioEntry.files` item to a `/`-separated path inside the assets folder,
+/// rejecting anything that would escape it.
+fn asset_path(file: &str) -> Result<String, String> {
+    let mut parts = Vec::new();
+    for part in file.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => return Err(format!("Caminho de arquivo inválido: {}", file)),
+            p if p.contains(':') => return Err(format!("Caminho de arquivo inválido: {}", file)),
+            p => parts.push(p),
+        }
+    }
+    if parts.is_empty() {
+        return Err(format!("Caminho de arquivo inválido: {}", file));
+    }
+    Ok(parts.join("/"))
+}
 
 fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -111,7 +295,7 @@ fn is_audio_file(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
-fn analyze_ogg_file(path: &Path) -> AudioFileInfo {
+pub fn analyze_ogg_file(path: &Path) -> AudioFileInfo {
     let name = path
         .file_name()
         .and_then(|n| n.to_str())
@@ -128,13 +312,13 @@ fn analyze_ogg_file(path: &Path) -> AudioFileInfo {
                 path: path_str,
                 size_bytes: 0,
                 size_display: "0 B".to_string(),
-                is_valid_ogg: false,
                 is_vorbis: false,
                 error: Some(format!("Erro ao ler arquivo: {}", e)),
                 duration_secs: None,
                 sample_rate: None,
                 channels: None,
                 format,
+                ..Default::default()
             };
         }
     };
@@ -142,163 +326,395 @@ fn analyze_ogg_file(path: &Path) -> AudioFileInfo {
     let size_bytes = metadata.len();
     let size_display = format_size(size_bytes);
 
-    if format != "OGG" {
-        let is_valid = format == "WAV";
-        return AudioFileInfo {
-            name,
-            path: path_str,
-            size_bytes,
-            size_display,
-            is_valid_ogg: is_valid,
-            is_vorbis: false,
-            error: if is_valid { None } else { Some(format!("Formato {} - converter para OGG Vorbis", format)) },
-            duration_secs: None,
-            sample_rate: None,
-            channels: None,
-            format,
-        };
+    let detected_format = fs::File::open(path)
+        .and_then(|mut f| sniff::detect_format(&mut f))
+        .ok()
+        .flatten()
+        .map(String::from);
+
+    let info = AudioFileInfo {
+        name,
+        path: path_str,
+        size_bytes,
+        size_display,
+        format: format.clone(),
+        detected_format: detected_format.clone(),
+        ..Default::default()
+    };
+
+    // Whether the game accepts what was found is up to `rules`.
+    match detected_format.as_deref().unwrap_or(&format) {
+        "OGG" => analyze_vorbis_file(path, info),
+        "WAV" => analyze_wav_file(path, info),
+        _ => info,
     }
+}
 
+fn analyze_vorbis_file(path: &Path, info: AudioFileInfo) -> AudioFileInfo {
     let file = match fs::File::open(path) {
         Ok(f) => f,
         Err(e) => {
             return AudioFileInfo {
-                name,
-                path: path_str,
-                size_bytes,
-                size_display,
-                is_valid_ogg: false,
-                is_vorbis: false,
                 error: Some(format!("Erro ao abrir: {}", e)),
-                duration_secs: None,
-                sample_rate: None,
-                channels: None,
-                format,
+                ..info
             };
         }
     };
 
     let mut reader = ogg::PacketReader::new(file);
 
-    match reader.read_packet() {
-        Ok(Some(packet)) => {
-            let is_vorbis = packet.data.len() >= 7
-                && packet.data[0] == 0x01
-                && &packet.data[1..7] == b"vorbis";
-
-            if is_vorbis && packet.data.len() >= 30 {
-                let channels = packet.data[11];
-                let sample_rate = u32::from_le_bytes([
-                    packet.data[12],
-                    packet.data[13],
-                    packet.data[14],
-                    packet.data[15],
-                ]);
-
-                let bitrate_nominal = i32::from_le_bytes([
-                    packet.data[20],
-                    packet.data[21],
-                    packet.data[22],
-                    packet.data[23],
-                ]);
-                let duration = if bitrate_nominal > 0 {
-                    Some((size_bytes as f64 * 8.0) / bitrate_nominal as f64)
-                } else {
-                    None
-                };
+    let mut stream_codecs = Vec::new();
+    let mut vorbis_stream = None;
 
-                AudioFileInfo {
-                    name,
-                    path: path_str,
-                    size_bytes,
-                    size_display,
-                    is_valid_ogg: true,
-                    is_vorbis: true,
-                    error: None,
-                    duration_secs: duration,
-                    sample_rate: Some(sample_rate),
-                    channels: Some(channels),
-                    format,
-                }
-            } else {
-                AudioFileInfo {
-                    name,
-                    path: path_str,
-                    size_bytes,
-                    size_display,
-                    is_valid_ogg: true,
-                    is_vorbis: false,
-                    error: Some("OGG Opus - Stardew Valley requer OGG Vorbis!".to_string()),
-                    duration_secs: None,
-                    sample_rate: None,
-                    channels: None,
-                    format,
+    let next_packet = loop {
+        match reader.read_packet() {
+            Ok(Some(packet)) if packet.first_in_stream() => {
+                stream_codecs.push(vorbis::identify_codec(&packet.data));
+                if vorbis_stream.is_none() {
+                    vorbis_stream = vorbis::parse_identification_header(&packet.data)
+                        .map(|ident| (ident, packet.stream_serial()));
                 }
             }
+            Ok(packet) => break packet,
+            Err(e) => {
+                return AudioFileInfo {
+                    error: Some(format!("Erro ao analisar OGG: {}", e)),
+                    ..info
+                };
+            }
         }
-        Ok(None) => {
-            AudioFileInfo {
-                name,
-                path: path_str,
-                size_bytes,
-                size_display,
-                is_valid_ogg: false,
-                is_vorbis: false,
-                error: Some("Arquivo OGG vazio".to_string()),
-                duration_secs: None,
-                sample_rate: None,
-                channels: None,
-                format,
+    };
+
+    if stream_codecs.is_empty() {
+        return AudioFileInfo {
+            error: Some("Arquivo OGG vazio".to_string()),
+            ..info
+        };
+    }
+
+    let Some((ident, serial)) = vorbis_stream else {
+        return AudioFileInfo {
+            codec: Some(stream_codecs.join(" + ")),
+            ..info
+        };
+    };
+
+    let comments = match next_packet {
+        Some(packet) if packet.stream_serial() == serial => vorbis::parse_comment_header(&packet.data),
+        _ => vorbis::read_comment_header(&mut reader, serial),
+    }
+    .unwrap_or_default();
+
+    let loop_points = comments
+        .loop_samples(ident.sample_rate)
+        .filter(|_| ident.sample_rate > 0)
+        .map(|(start, end)| {
+            let rate = ident.sample_rate as f64;
+            LoopPoints {
+                start_sample: start,
+                end_sample: end,
+                start_secs: start as f64 / rate,
+                end_secs: end.map(|e| e as f64 / rate),
             }
+        });
+    let mut file = reader.into_inner();
+
+    let total_samples = match vorbis::last_granule_position(&mut file, serial) {
+        Ok(granule) => granule,
+        Err(e) => {
+            log::warn!("Could not read last Ogg page of {}: {}", info.path, e);
+            None
         }
+    };
+
+    let duration = match total_samples {
+        Some(granule) if ident.sample_rate > 0 => Some(granule as f64 / ident.sample_rate as f64),
+        _ if ident.bitrate_nominal > 0 => {
+            Some((info.size_bytes as f64 * 8.0) / ident.bitrate_nominal as f64)
+        }
+        _ => None,
+    };
+
+    AudioFileInfo {
+        is_vorbis: true,
+        error: None,
+        duration_secs: duration,
+        sample_rate: Some(ident.sample_rate),
+        channels: Some(ident.channels),
+        codec: Some("Vorbis".to_string()),
+        title: comments.get("TITLE").map(String::from),
+        artist: comments.get("ARTIST").map(String::from),
+        album: comments.get("ALBUM").map(String::from),
+        vendor: Some(comments.vendor).filter(|v| !v.is_empty()),
+        tags: comments.tags,
+        loop_points,
+        total_samples,
+        ..info
+    }
+}
+
+fn analyze_wav_file(path: &Path, info: AudioFileInfo) -> AudioFileInfo {
+    let mut file = match fs::File::open(path) {
+        Ok(f) => f,
         Err(e) => {
-            AudioFileInfo {
-                name,
-                path: path_str,
-                size_bytes,
-                size_display,
-                is_valid_ogg: false,
-                is_vorbis: false,
-                error: Some(format!("Erro ao analisar OGG: {}", e)),
-                duration_secs: None,
-                sample_rate: None,
-                channels: None,
-                format,
+            return AudioFileInfo {
+                error: Some(format!("Erro ao abrir: {}", e)),
+                ..info
+            };
+        }
+    };
+
+    match wav::read_wav_info(&mut file) {
+        Ok(wav) => AudioFileInfo {
+            duration_secs: wav.duration_secs(),
+            total_samples: wav.frames(),
+            sample_rate: Some(wav.sample_rate),
+            channels: Some(wav.channels.min(u8::MAX as u16) as u8),
+            codec: Some(wav.codec_name()),
+            bits_per_sample: Some(wav.bits_per_sample),
+            ..info
+        },
+        Err(e) => AudioFileInfo {
+            error: Some(format!("Erro ao analisar WAV: {}", e)),
+            ..info
+        },
+    }
+}
+
+fn validate_ogg_pages(path: &Path, info: AudioFileInfo) -> AudioFileInfo {
+    let file = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(e) => {
+            return AudioFileInfo {
+                error: Some(format!("Erro ao abrir: {}", e)),
+                ..info
+            };
+        }
+    };
+
+    match vorbis::verify_pages(file) {
+        Ok(stats) => {
+            log::debug!("{}: {} pages in {} streams OK", info.name, stats.pages, stats.streams);
+            info
+        }
+        Err(e) => AudioFileInfo {
+            error: Some(format!("OGG corrompido no byte {}: {}", e.offset, e.message)),
+            ..info
+        },
+    }
+}
+
+fn decode_test(path: &Path, info: AudioFileInfo) -> AudioFileInfo {
+    let decode_check = match decode::decode_file(path, |_, _, _| {}) {
+        Ok(summary) => DecodeCheck {
+            length_matches: info
+                .total_samples
+                .map(|expected| summary.frames.abs_diff(expected) <= DECODE_LENGTH_TOLERANCE),
+            decoded_samples: summary.frames,
+            error_count: summary.error_count,
+            errors: summary.errors,
+        },
+        Err(e) => DecodeCheck {
+            decoded_samples: 0,
+            error_count: 1,
+            errors: vec![e],
+            length_matches: None,
+        },
+    };
+
+    AudioFileInfo {
+        decode_check: Some(decode_check),
+        ..info
+    }
+}
+
+/// Set by `cancel_scan`; workers check it before starting each file.
+static SCAN_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+fn scan_file(path: &Path, options: &ScanOptions, silence_threshold_db: f64) -> AudioFileInfo {
+    let mut info = analyze_ogg_file(path);
+    if options.deep_validation && info.detected_format.as_deref() == Some("OGG") {
+        info = validate_ogg_pages(path, info);
+    }
+    if options.decode_test && info.is_vorbis {
+        info = decode_test(path, info);
+    }
+    let decodable = info.is_vorbis || info.detected_format.as_deref() == Some("WAV");
+    if (options.loudness || options.silence) && decodable {
+        match analysis::analyze_levels(path, silence_threshold_db) {
+            Ok(levels) => {
+                info.loudness = options.loudness.then_some(levels.loudness);
+                info.silence = options.silence.then_some(levels.silence);
             }
+            Err(e) => log::warn!("Level analysis failed for {}: {}", info.name, e),
         }
     }
+    if options.audio_fingerprint && decodable {
+        match analysis::pcm_fingerprint(path) {
+            Ok(fingerprint) => info.audio_fingerprint = Some(fingerprint),
+            Err(e) => log::warn!("Audio fingerprint failed for {}: {}", info.name, e),
+        }
+    }
+    info
+}
+
+/// Full validation of a single file with every rule enabled, used to check conversion output.
+pub fn verify_file(path: &Path) -> AudioFileInfo {
+    let options = ScanOptions {
+        deep_validation: true,
+        decode_test: true,
+        ..Default::default()
+    };
+    rules::apply(scan_file(path, &options, analysis::DEFAULT_SILENCE_THRESHOLD_DB), &[])
 }
 
 #[tauri::command]
-pub async fn scan_audio_folder(folder_path: String) -> Result<ScanResult, String> {
+pub async fn scan_audio_folder(
+    app_handle: AppHandle,
+    folder_path: String,
+    options: Option<ScanOptions>,
+) -> Result<ScanResult, String> {
+    // A scan keeps every core busy for minutes; run it off the async runtime so `cancel_scan`
+    // still gets a worker.
+    tauri::async_runtime::spawn_blocking(move || scan_folder(app_handle, folder_path, options))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn scan_folder(app_handle: AppHandle, folder_path: String, options: Option<ScanOptions>) -> Result<ScanResult, String> {
     log::info!("🔍 Scanning folder: {}", folder_path);
 
+    let options = options.unwrap_or_default();
+    let silence_threshold_db = options
+        .silence_threshold_db
+        .unwrap_or(analysis::DEFAULT_SILENCE_THRESHOLD_DB);
+
     let path = Path::new(&folder_path);
     if !path.exists() {
         return Err("Pasta não encontrada".to_string());
     }
 
-    let mut files = Vec::new();
-    let mut total_size: u64 = 0;
+    let cache_path = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(cache::CACHE_FILE);
+    let mut scan_cache = ScanCache::load(&cache_path);
+
+    SCAN_CANCELLED.store(false, Ordering::SeqCst);
+
+    let walk::WalkOutcome {
+        files: paths,
+        warnings,
+    } = walk::collect_files(path, &options.walk, is_audio_file)?;
+
+    let total = paths.len();
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let cached_hits = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(total));
+    let cache_updates = Mutex::new(Vec::new());
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .min(total.max(1));
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                if SCAN_CANCELLED.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Some(entry_path) = paths.get(next.fetch_add(1, Ordering::SeqCst)) else {
+                    break;
+                };
+
+                let fingerprint = Fingerprint::read(entry_path, options.verify_hash).ok();
+                let cached = fingerprint
+                    .as_ref()
+                    .filter(|_| !options.refresh)
+                    .and_then(|fp| scan_cache.get(entry_path, fp, &options))
+                    .cloned();
+
+                let (mut info, analyzed_with, mut stale) = match cached {
+                    Some(entry) => {
+                        cached_hits.fetch_add(1, Ordering::SeqCst);
+                        (entry.info, entry.options, false)
+                    }
+                    None => (scan_file(entry_path, &options, silence_threshold_db), options.clone(), true),
+                };
+
+                if options.find_duplicates && info.content_hash.is_none() {
+                    info.content_hash = fingerprint
+                        .as_ref()
+                        .and_then(|fp| fp.content_hash.clone())
+                        .or_else(|| cache::content_hash(entry_path).ok());
+                    stale = true;
+                }
+
+                if let Some(fingerprint) = fingerprint.filter(|_| stale) {
+                    cache_updates.lock().unwrap().push((
+                        entry_path,
+                        CacheEntry {
+                            fingerprint,
+                            options: analyzed_with,
+                            info: info.clone(),
+                        },
+                    ));
+                }
+                info.relative_path = walk::relative(path, entry_path);
+                let info = rules::apply(info, &options.disabled_rules);
+                let current_file = info.relative_path.clone();
+                results.lock().unwrap().push(info);
+
+                let _ = app_handle.emit(
+                    "scan-progress",
+                    ScanProgress {
+                        done: done.fetch_add(1, Ordering::SeqCst) + 1,
+                        total,
+                        current_file,
+                    },
+                );
+            });
+        }
+    });
+
+    let cancelled = SCAN_CANCELLED.swap(false, Ordering::SeqCst);
 
-    for entry in walkdir::WalkDir::new(path)
-        .follow_links(true)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let entry_path = entry.path();
-        if entry_path.is_file() && is_audio_file(entry_path) {
-            let info = analyze_ogg_file(entry_path);
-            total_size += info.size_bytes;
-            files.push(info);
+    for (entry_path, entry) in cache_updates.into_inner().unwrap() {
+        scan_cache.insert(entry_path, entry);
+    }
+    if !cancelled {
+        let seen = paths.iter().map(|p| cache::key(p)).collect();
+        let pruned = scan_cache.prune(&seen);
+        if pruned > 0 {
+            log::info!("🧹 Pruned {} stale cache entries", pruned);
         }
     }
+    if let Err(e) = scan_cache.save(&cache_path) {
+        log::warn!("Failed to save scan cache: {}", e);
+    }
+    log::info!(
+        "⚡ {} of {} files served from cache ({} cached in total)",
+        cached_hits.load(Ordering::SeqCst),
+        total,
+        scan_cache.len()
+    );
+    let mut files = results.into_inner().unwrap();
+    files.sort_by_key(|f| f.relative_path.to_lowercase());
 
-    files.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-
-    let total_valid = files.iter().filter(|f| f.is_vorbis).count();
+    let total_size: u64 = files.iter().map(|f| f.size_bytes).sum();
+    let summary = ScanSummary::from_files(&files);
+    let total_valid = summary.total_valid;
     let total_invalid = files.len() - total_valid;
+    let duplicates = duplicates::find_duplicates(&files);
 
+    if !duplicates.is_empty() {
+        log::info!("🧬 Found {} duplicate groups", duplicates.len());
+    }
+    if cancelled {
+        log::info!("⏹️ Scan cancelled after {} of {} files", files.len(), total);
+    }
     log::info!(
         "✅ Scan complete: {} files ({} valid, {} invalid)",
         files.len(),
@@ -312,9 +728,151 @@ pub async fn scan_audio_folder(folder_path: String) -> Result<ScanResult, String
         total_valid,
         total_invalid,
         total_size: format_size(total_size),
+        cancelled,
+        duplicates,
+        warnings,
+        summary,
     })
 }
 
+/// Writes a scan summary as JSON or CSV, chosen by the file extension.
+#[tauri::command]
+pub async fn export_scan_summary(file_path: String, summary: ScanSummary) -> Result<(), String> {
+    log::info!("📊 Exporting scan summary to: {}", file_path);
+
+    let extension = Path::new(&file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    let content = match extension.as_deref() {
+        Some("json") => serde_json::to_string_pretty(&summary).map_err(|e| e.to_string())?,
+        Some("csv") => summary.to_csv(),
+        _ => return Err("Formato de resumo não suportado - use .json ou .csv".to_string()),
+    };
+
+    fs::write(&file_path, content).map_err(|e| format!("Erro ao salvar: {}", e))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_scan_rules() -> Vec<RuleInfo> {
+    rules::rule_list()
+}
+
+/// Stops the running scan; `scan_audio_folder` then returns the files analyzed so far.
+#[tauri::command]
+pub async fn cancel_scan() -> Result<(), String> {
+    log::info!("⏹️ Cancelling scan");
+    SCAN_CANCELLED.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn check_audio_entries(
+    audio_source_folder: String,
+    audios: Vec<AudioEntry>,
+    silence_threshold_db: Option<f64>,
+) -> Result<Vec<AudioEntryWarning>, String> {
+    let source_path = Path::new(&audio_source_folder);
+    if !source_path.exists() {
+        return Err("Pasta não encontrada".to_string());
+    }
+
+    let silence_threshold_db = silence_threshold_db.unwrap_or(analysis::DEFAULT_SILENCE_THRESHOLD_DB);
+
+    let mut warnings = Vec::new();
+
+    for audio in &audios {
+        if audio.category != "Music" {
+            continue;
+        }
+
+        for file in &audio.files {
+            let file_path = match asset_path(file) {
+                Ok(asset) => source_path.join(asset),
+                Err(message) => {
+                    warnings.push(AudioEntryWarning {
+                        audio_id: audio.id.clone(),
+                        file: file.clone(),
+                        message,
+                    });
+                    continue;
+                }
+            };
+
+            let levels = match analysis::analyze_levels(&file_path, silence_threshold_db) {
+                Ok(levels) => Some(levels),
+                Err(e) => {
+                    log::warn!("Level analysis failed for {}: {}", file, e);
+                    None
+                }
+            };
+
+            if let Some(lufs) = levels.and_then(|l| l.loudness.integrated_lufs) {
+                if lufs > VANILLA_MUSIC_LUFS + LOUDNESS_WARNING_LU {
+                    warnings.push(AudioEntryWarning {
+                        audio_id: audio.id.clone(),
+                        file: file.clone(),
+                        message: format!(
+                            "Volume de {:.1} LUFS - {:.1} LU acima da trilha original (~{:.0} LUFS)",
+                            lufs,
+                            lufs - VANILLA_MUSIC_LUFS,
+                            VANILLA_MUSIC_LUFS
+                        ),
+                    });
+                }
+            }
+
+            if !audio.looped {
+                continue;
+            }
+
+            if let Some(levels) = levels {
+                let silence = levels.silence;
+                let message = if levels.loudness.sample_peak_dbfs.is_none_or(|peak| peak < silence.threshold_db) {
+                    Some(format!(
+                        "Arquivo inteiro em silêncio (abaixo de {:.0} dBFS)",
+                        silence.threshold_db
+                    ))
+                } else if silence.leading_secs > LOOP_GAP_WARNING_SECS
+                    || silence.trailing_secs > LOOP_GAP_WARNING_SECS
+                {
+                    Some(format!(
+                        "Silêncio de {:.2}s no início e {:.2}s no fim (abaixo de {:.0} dBFS) - lacuna audível a cada loop",
+                        silence.leading_secs, silence.trailing_secs, silence.threshold_db
+                    ))
+                } else {
+                    None
+                };
+
+                if let Some(message) = message {
+                    warnings.push(AudioEntryWarning {
+                        audio_id: audio.id.clone(),
+                        file: file.clone(),
+                        message,
+                    });
+                }
+            }
+
+            if let Some(loop_points) = &analyze_ogg_file(&file_path).loop_points {
+                if loop_points.start_secs > LONG_INTRO_SECS {
+                    warnings.push(AudioEntryWarning {
+                        audio_id: audio.id.clone(),
+                        file: file.clone(),
+                        message: format!(
+                            "Introdução de {:.1}s antes do LOOPSTART - o Looped do jogo repete o arquivo inteiro, incluindo a introdução",
+                            loop_points.start_secs
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    log::info!("🔁 Checked {} audios: {} warnings", audios.len(), warnings.len());
+    Ok(warnings)
+}
+
 #[tauri::command]
 pub async fn watch_assets_folder(app_handle: AppHandle, folder_path: String) -> Result<(), String> {
     use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
@@ -455,11 +1013,11 @@ pub fn generate_content_json(audios: Vec<AudioEntry>) -> Result<String, String>
         let mut entries = serde_json::Map::new();
 
         for audio in &audios {
-            let file_paths: Vec<String> = audio
+            let file_paths = audio
                 .files
                 .iter()
-                .map(|f| format!("{{{{AbsoluteFilePath: assets/{}}}}}", f))
-                .collect();
+                .map(|f| asset_path(f).map(|f| format!("{{{{AbsoluteFilePath: assets/{}}}}}", f)))
+                .collect::<Result<Vec<String>, String>>()?;
 
             let mut entry = serde_json::json!({
                 "Id": audio.id,
@@ -570,8 +1128,9 @@ pub async fn export_to_folder(
             let source_path = Path::new(&source);
             for audio in &audios {
                 for file in &audio.files {
-                    let src = source_path.join(file);
-                    let dst = mod_folder.join("assets").join(file);
+                    let file = asset_path(file)?;
+                    let src = source_path.join(&file);
+                    let dst = mod_folder.join("assets").join(&file);
 
                     if src.exists() {
                         if let Some(parent) = dst.parent() {
@@ -651,7 +1210,8 @@ pub async fn export_to_zip(
             let source_path = Path::new(&source);
             for audio in &audios {
                 for file in &audio.files {
-                    let src = source_path.join(file);
+                    let file = asset_path(file)?;
+                    let src = source_path.join(&file);
                     if src.exists() {
                         if let Ok(data) = fs::read(&src) {
                             zip.start_file(format!("{}/assets/{}", prefix, file), options)
@@ -684,110 +1244,266 @@ pub async fn convert_audio(
     source_path: String,
     target_format: String,
     output_dir: Option<String>,
+    options: Option<EncodingOptions>,
+    output: Option<OutputOptions>,
 ) -> Result<ConvertResult, String> {
-    log::info!(
-        "🔄 Converting {} to {}",
-        source_path,
-        target_format.to_uppercase()
-    );
+    let options = options.unwrap_or_default();
+    let output = output.unwrap_or_default();
+    convert::convert_file(&source_path, &target_format, output_dir.as_deref(), &options, &output, None, &Monitor::NONE)
+}
+
+#[tauri::command]
+pub fn list_encoding_presets() -> Vec<EncodingPreset> {
+    convert::builtin_presets()
+}
+
+/// The encoding options saved by `save_encoding_settings`, if any.
+#[tauri::command]
+pub async fn load_encoding_settings(app_handle: AppHandle) -> Result<Option<EncodingOptions>, String> {
+    let path = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(convert::SETTINGS_FILE);
 
-    let source = Path::new(&source_path);
-    if !source.exists() {
-        return Err("Arquivo fonte não encontrado".to_string());
+    if !path.exists() {
+        return Ok(None);
     }
 
-    let stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
-    let extension = match target_format.to_lowercase().as_str() {
-        "ogg" | "vorbis" => "ogg",
-        "wav" => "wav",
-        _ => return Err(format!("Formato não suportado: {}", target_format)),
-    };
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    Ok(serde_json::from_str(&content).ok())
+}
 
-    let output_path = if let Some(dir) = output_dir {
-        Path::new(&dir).join(format!("{}.{}", stem, extension))
+#[tauri::command]
+pub async fn save_encoding_settings(app_handle: AppHandle, options: EncodingOptions) -> Result<(), String> {
+    options.validate()?;
+    let app_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+
+    fs::create_dir_all(&app_dir).map_err(|e| e.to_string())?;
+
+    let json = serde_json::to_string_pretty(&options).map_err(|e| e.to_string())?;
+    fs::write(app_dir.join(convert::SETTINGS_FILE), json)
+        .map_err(|e| format!("Erro ao salvar as opções de codificação: {}", e))
+}
+
+/// Which FFmpeg/ffprobe the fallback converter would use. The probe is cached; `refresh`
+/// runs it again.
+#[tauri::command]
+pub async fn get_toolchain_status(refresh: Option<bool>) -> ToolchainStatus {
+    if refresh.unwrap_or(false) {
+        toolchain::refresh()
     } else {
-        source.with_extension(extension)
-    };
+        toolchain::status()
+    }
+}
 
-    let mut cmd = Command::new("ffmpeg");
-    cmd.arg("-y")
-        .arg("-i")
-        .arg(&source_path);
-
-    match extension {
-        "ogg" => {
-            cmd.arg("-c:a")
-                .arg("libvorbis")          
-                .arg("-q:a")
-                .arg("6")                  
-                .arg("-ar")
-                .arg("44100")              
-                .arg("-ac")
-                .arg("2");                 
+/// Remembers the FFmpeg binary (or its folder) chosen by the user; `None` goes back to searching.
+#[tauri::command]
+pub async fn set_ffmpeg_path(app_handle: AppHandle, path: Option<String>) -> Result<ToolchainStatus, String> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(p) = &path {
+        if !Path::new(p).exists() {
+            return Err(format!("Caminho não encontrado: {}", p));
         }
-        "wav" => {
-            cmd.arg("-c:a")
-                .arg("pcm_s16le")          
-                .arg("-ar")
-                .arg("44100")              
-                .arg("-ac")
-                .arg("2");               
+    }
+
+    let previous = toolchain::status().configured_path;
+    toolchain::configure(path.clone().map(PathBuf::from));
+    let status = toolchain::status();
+    if let Some(p) = &path {
+        if !status.ffmpeg.as_ref().is_some_and(|tool| tool.source == ToolSource::Configured) {
+            toolchain::configure(previous.map(PathBuf::from));
+            return Err(format!("Nenhum FFmpeg utilizável em {}", p));
         }
-        _ => {}
     }
 
-    cmd.arg(&output_path);
+    let settings_path = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(toolchain::SETTINGS_FILE);
+    ToolchainSettings {
+        ffmpeg_path: path.clone(),
+    }
+    .save(&settings_path)?;
+
+    log::info!("🧰 FFmpeg path set to {:?}", path);
+    Ok(status)
+}
+
+/// Set by `cancel_conversion` without a job id; stops every job of the running batch.
+static BATCH_CANCELLED: AtomicBool = AtomicBool::new(false);
+/// Ids of single jobs cancelled by `cancel_conversion`.
+static CANCELLED_JOBS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Called when a batch ends rather than when it starts, so a cancel sent just as the batch
+/// starts still reaches it.
+fn reset_conversion_cancel() {
+    BATCH_CANCELLED.store(false, Ordering::SeqCst);
+    CANCELLED_JOBS.lock().unwrap().clear();
+}
+
+/// Progress events are only sent when a job moves forward by at least this much.
+const CONVERT_PROGRESS_STEP: f32 = 0.01;
+
+/// Converts `jobs` on a bounded pool of worker threads, emitting `convert-progress` for
+/// every status change, and returns how each job ended.
+#[tauri::command]
+pub async fn convert_batch(
+    app_handle: AppHandle,
+    jobs: Vec<ConvertJobRequest>,
+    target_format: String,
+    output_dir: Option<String>,
+    options: Option<EncodingOptions>,
+    output: Option<OutputOptions>,
+    max_workers: Option<usize>,
+) -> Result<BatchConvertResult, String> {
+    // Like `scan_audio_folder`, kept off the async runtime so `cancel_conversion` can run.
+    tauri::async_runtime::spawn_blocking(move || {
+        convert_jobs(app_handle, jobs, target_format, output_dir, options, output, max_workers)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
 
-    log::debug!("Running: {:?}", cmd);
+fn convert_jobs(
+    app_handle: AppHandle,
+    jobs: Vec<ConvertJobRequest>,
+    target_format: String,
+    output_dir: Option<String>,
+    options: Option<EncodingOptions>,
+    output: Option<OutputOptions>,
+    max_workers: Option<usize>,
+) -> Result<BatchConvertResult, String> {
+    log::info!("🔄 Converting {} files to {}", jobs.len(), target_format.to_uppercase());
+    let options = options.unwrap_or_default();
+    options.validate().inspect_err(|_| reset_conversion_cancel())?;
+    let output = output.unwrap_or_default();
+
+    let emit = |job_id: &str, status, progress, error: Option<String>| {
+        let _ = app_handle.emit(
+            "convert-progress",
+            ConvertProgress {
+                job_id: job_id.to_string(),
+                status,
+                progress,
+                error,
+            },
+        );
+    };
+    for job in &jobs {
+        emit(&job.id, ConvertJobStatus::Queued, 0.0, None);
+    }
 
-    let output = cmd.output().map_err(|e| {
-        if e.kind() == std::io::ErrorKind::NotFound {
-            "FFmpeg não encontrado! Instale com:\n\n\
-            • CachyOS/Arch: sudo pacman -S ffmpeg\n\
-            • Ubuntu/Debian: sudo apt install ffmpeg\n\
-            • Fedora: sudo dnf install ffmpeg\n\
-            • macOS: brew install ffmpeg\n\
-            • Windows: winget install ffmpeg".to_string()
-        } else {
-            format!("Erro ao executar FFmpeg: {}", e)
+    let next = AtomicUsize::new(0);
+    let written = BatchOutputs::default();
+    let outcomes = Mutex::new(Vec::with_capacity(jobs.len()));
+    let workers = max_workers
+        .filter(|&n| n > 0)
+        .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4))
+        .min(jobs.len().max(1));
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while let Some(job) = jobs.get(next.fetch_add(1, Ordering::SeqCst)) {
+                    let is_cancelled = || {
+                        BATCH_CANCELLED.load(Ordering::SeqCst) || CANCELLED_JOBS.lock().unwrap().contains(&job.id)
+                    };
+
+                    let outcome = if is_cancelled() {
+                        Err(convert::CANCELLED.to_string())
+                    } else {
+                        emit(&job.id, ConvertJobStatus::Converting, 0.0, None);
+                        let last_sent = Mutex::new(0.0f32);
+                        let on_progress = |progress: f32| {
+                            let mut last = last_sent.lock().unwrap();
+                            if progress - *last >= CONVERT_PROGRESS_STEP {
+                                *last = progress;
+                                emit(&job.id, ConvertJobStatus::Converting, progress, None);
+                            }
+                        };
+                        let monitor = Monitor {
+                            on_progress: &on_progress,
+                            is_cancelled: &is_cancelled,
+                        };
+                        convert::convert_file(
+                            &job.source_path,
+                            &target_format,
+                            output_dir.as_deref(),
+                            &options,
+                            &output,
+                            Some(&written),
+                            &monitor,
+                        )
+                    };
+
+                    let (status, result, error) = match outcome {
+                        Ok(result) if result.skipped => (ConvertJobStatus::Skipped, Some(result), None),
+                        Ok(result) => (ConvertJobStatus::Done, Some(result), None),
+                        Err(e) if e == convert::CANCELLED => (ConvertJobStatus::Cancelled, None, None),
+                        Err(e) => (ConvertJobStatus::Failed, None, Some(e)),
+                    };
+                    let progress = if matches!(status, ConvertJobStatus::Done | ConvertJobStatus::Skipped) { 1.0 } else { 0.0 };
+                    emit(&job.id, status, progress, error.clone());
+
+                    outcomes.lock().unwrap().push(ConvertJobOutcome {
+                        id: job.id.clone(),
+                        source_path: job.source_path.clone(),
+                        status,
+                        result,
+                        error,
+                    });
+                }
+            });
         }
-    })?;
-
-    if output.status.success() {
-        log::info!("✅ Conversion complete: {:?}", output_path);
-        
-        let verify_cmd = Command::new("ffprobe")
-            .args(&[
-                "-v", "error",
-                "-select_streams", "a:0",
-                "-show_entries", "stream=codec_name",
-                "-of", "default=noprint_wrappers=1:nokey=1",
-                output_path.to_str().unwrap()
-            ])
-            .output();
-            
-        if let Ok(verify_output) = verify_cmd {
-            let codec = String::from_utf8_lossy(&verify_output.stdout).trim().to_string();
-            log::info!("📊 Codec verificado: {}", codec);
-            
-            if extension == "ogg" && codec != "vorbis" {
-                log::warn!("⚠️ AVISO: Arquivo OGG mas codec é '{}', não 'vorbis'!", codec);
-                return Err(format!(
-                    "Conversão falhou: arquivo gerado com codec '{}' ao invés de 'vorbis'.\n\
-                    Verifique se FFmpeg foi compilado com suporte a libvorbis.",
-                    codec
-                ));
-            }
+    });
+
+    reset_conversion_cancel();
+
+    let mut outcomes = outcomes.into_inner().unwrap();
+    let order: HashMap<&str, usize> = jobs.iter().enumerate().map(|(i, j)| (j.id.as_str(), i)).collect();
+    outcomes.sort_by_key(|o| order.get(o.id.as_str()).copied());
+
+    let count = |status| outcomes.iter().filter(|o| o.status == status).count();
+    let (succeeded, skipped, failed, cancelled) = (
+        count(ConvertJobStatus::Done),
+        count(ConvertJobStatus::Skipped),
+        count(ConvertJobStatus::Failed),
+        count(ConvertJobStatus::Cancelled),
+    );
+    log::info!(
+        "✅ Batch conversion finished: {} done, {} skipped, {} failed, {} cancelled",
+        succeeded,
+        skipped,
+        failed,
+        cancelled
+    );
+
+    Ok(BatchConvertResult {
+        jobs: outcomes,
+        succeeded,
+        skipped,
+        failed,
+        cancelled,
+    })
+}
+
+/// Cancels one job of the running batch, or the whole batch when `job_id` is omitted.
+#[tauri::command]
+pub async fn cancel_conversion(job_id: Option<String>) -> Result<(), String> {
+    match job_id {
+        Some(id) => {
+            log::info!("⏹️ Cancelling conversion job {}", id);
+            CANCELLED_JOBS.lock().unwrap().push(id);
+        }
+        None => {
+            log::info!("⏹️ Cancelling conversion batch");
+            BATCH_CANCELLED.store(true, Ordering::SeqCst);
         }
-        
-        Ok(ConvertResult {
-            success: true,
-            output_path: output_path.to_string_lossy().to_string(),
-            message: format!("Convertido para {} com sucesso", extension.to_uppercase()),
-        })
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        log::error!("❌ FFmpeg error: {}", stderr);
-        Err(format!("Erro FFmpeg: {}", stderr))
     }
+    Ok(())
 }
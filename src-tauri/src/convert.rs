@@ -0,0 +1,1235 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, ErrorKind, Read};
+use std::num::{NonZeroU32, NonZeroU8};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use symphonia::core::audio::Channels;
+use vorbis_rs::{VorbisBitrateManagementStrategy, VorbisEncoder, VorbisEncoderBuilder};
+
+use crate::analysis::{LoudnessMeter, LoudnessStats, SilenceDetector};
+use crate::commands::{self, AudioFileInfo, ConvertResult};
+use crate::decode;
+use crate::edit::{EditOptions, EditPlan, EditReport, Editor, Trim};
+use crate::resample::Resampler;
+use crate::rules::{self, Severity};
+use crate::toolchain;
+use crate::wav::WavWriter;
+
+pub const CANCELLED: &str = "Conversão cancelada";
+
+/// Last encoding options chosen in the converter, kept in the app data folder.
+pub const SETTINGS_FILE: &str = "encoding.json";
+
+/// How far the output duration may drift from the expected one, covering encoder priming
+/// and resampler rounding.
+const DURATION_TOLERANCE_SECS: f64 = 0.1;
+
+/// Upper bound on `name (n).ext` candidates tried before giving up.
+const MAX_NAME_SUFFIX: u32 = 1000;
+
+/// Roughly what `ffmpeg -q:a 6` produces.
+pub const DEFAULT_VORBIS_QUALITY: f32 = 0.6;
+
+const FRAC_1_SQRT_2: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoder {
+    /// Built-in decoders and libvorbis encoder, no external tools.
+    Native,
+    Ffmpeg,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelPolicy {
+    /// Mono and stereo stay as they are; surround is folded to stereo.
+    #[default]
+    Keep,
+    Mono,
+    Stereo,
+}
+
+/// How a conversion encodes its output. `quality` and `max_bitrate_kbps` only affect Vorbis.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct EncodingOptions {
+    /// Vorbis quality, -0.2 to 1.0 (`ffmpeg -q:a` divided by ten).
+    pub quality: f32,
+    /// Output sample rate; `None` keeps the source rate.
+    pub sample_rate: Option<u32>,
+    pub channels: ChannelPolicy,
+    /// Hard bitrate cap. When set the encoder switches from quality VBR to constrained ABR.
+    pub max_bitrate_kbps: Option<u32>,
+    pub normalize: Option<LoudnessTarget>,
+    pub edit: EditOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct LoudnessTarget {
+    pub target_lufs: f32,
+    pub true_peak_dbtp: f32,
+}
+
+impl Default for LoudnessTarget {
+    fn default() -> Self {
+        LoudnessTarget {
+            target_lufs: -16.0,
+            true_peak_dbtp: -1.0,
+        }
+    }
+}
+
+impl LoudnessTarget {
+    /// Gain that brings `before` to the target without pushing its true peak past the ceiling.
+    fn gain_db(&self, before: &LoudnessStats) -> f64 {
+        let Some(integrated) = before.integrated_lufs else {
+            return 0.0;
+        };
+        let gain = self.target_lufs as f64 - integrated;
+        match before.true_peak_dbtp {
+            Some(peak) => gain.min(self.true_peak_dbtp as f64 - peak),
+            None => gain,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LoudnessReport {
+    pub target_lufs: f32,
+    pub true_peak_ceiling_dbtp: f32,
+    pub before: LoudnessStats,
+    pub after: LoudnessStats,
+    pub gain_db: f64,
+}
+
+impl Default for EncodingOptions {
+    fn default() -> Self {
+        EncodingOptions {
+            quality: DEFAULT_VORBIS_QUALITY,
+            sample_rate: None,
+            channels: ChannelPolicy::Keep,
+            max_bitrate_kbps: None,
+            normalize: None,
+            edit: EditOptions::default(),
+        }
+    }
+}
+
+impl EncodingOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(-0.2..=1.0).contains(&self.quality) {
+            return Err(format!("Qualidade Vorbis inválida: {} (use de -0.2 a 1.0)", self.quality));
+        }
+        if let Some(rate) = self.sample_rate {
+            if !(8_000..=192_000).contains(&rate) {
+                return Err(format!("Taxa de amostragem inválida: {} Hz", rate));
+            }
+        }
+        if let Some(kbps) = self.max_bitrate_kbps {
+            if !(32..=500).contains(&kbps) {
+                return Err(format!("Bitrate máximo inválido: {} kbps (use de 32 a 500)", kbps));
+            }
+        }
+        if let Some(target) = &self.normalize {
+            if !(-40.0..=-5.0).contains(&target.target_lufs) {
+                return Err(format!("Loudness alvo inválido: {} LUFS (use de -40 a -5)", target.target_lufs));
+            }
+            if !(-9.0..=0.0).contains(&target.true_peak_dbtp) {
+                return Err(format!("Teto de true peak inválido: {} dBTP (use de -9 a 0)", target.true_peak_dbtp));
+            }
+        }
+        self.edit.validate()
+    }
+
+    fn output_channels(&self, source_channels: usize) -> usize {
+        match self.channels {
+            ChannelPolicy::Keep => source_channels.min(2),
+            ChannelPolicy::Mono => 1,
+            ChannelPolicy::Stereo => 2,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EncodingPreset {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub options: EncodingOptions,
+}
+
+/// Presets save space through quality and channels only; every rate is one the `sample_rate`
+/// rule accepts, so their output scans clean.
+pub fn builtin_presets() -> Vec<EncodingPreset> {
+    let preset = |id: &str, name: &str, description: &str, options: EncodingOptions| EncodingPreset {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: description.to_string(),
+        options,
+    };
+    vec![
+        preset(
+            "default",
+            "Padrão",
+            "Qualidade 6, mantém taxa e canais da fonte",
+            EncodingOptions::default(),
+        ),
+        preset(
+            "music_hq",
+            "Music HQ",
+            "Músicas: qualidade 8, estéreo, 44.1 kHz",
+            EncodingOptions {
+                quality: 0.8,
+                sample_rate: Some(44_100),
+                channels: ChannelPolicy::Stereo,
+                max_bitrate_kbps: None,
+                ..Default::default()
+            },
+        ),
+        preset(
+            "sfx_mono_small",
+            "SFX mono small",
+            "Efeitos curtos: qualidade 4, mono, 44.1 kHz",
+            EncodingOptions {
+                quality: 0.4,
+                sample_rate: Some(44_100),
+                channels: ChannelPolicy::Mono,
+                max_bitrate_kbps: None,
+                ..Default::default()
+            },
+        ),
+        preset(
+            "ambient_low",
+            "Ambient low",
+            "Ambientes longos: qualidade 3, 44.1 kHz, até 96 kbps",
+            EncodingOptions {
+                quality: 0.3,
+                sample_rate: Some(44_100),
+                channels: ChannelPolicy::Keep,
+                max_bitrate_kbps: Some(96),
+                ..Default::default()
+            },
+        ),
+    ]
+}
+
+/// What happens when the output file already exists.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionPolicy {
+    /// Writes `name (1).ext`, `name (2).ext`... instead.
+    #[default]
+    Suffix,
+    Overwrite,
+    Skip,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct OutputOptions {
+    pub collision: CollisionPolicy,
+    /// Folder that receives a copy of any file the conversion replaces, relative to that
+    /// file's folder unless absolute. Replacing the source itself is only allowed with it.
+    pub backup_dir: Option<String>,
+}
+
+/// Optional processing a conversion applied on top of the format change.
+#[derive(Debug, Default)]
+pub struct Applied {
+    pub loudness: Option<LoudnessReport>,
+    pub edit: Option<EditReport>,
+}
+
+/// Progress (0.0 to 1.0) and cancellation hooks for a running conversion.
+pub struct Monitor<'a> {
+    pub on_progress: &'a (dyn Fn(f32) + Sync),
+    pub is_cancelled: &'a (dyn Fn() -> bool + Sync),
+}
+
+impl Monitor<'_> {
+    pub const NONE: Monitor<'static> = Monitor {
+        on_progress: &|_| {},
+        is_cancelled: &|| false,
+    };
+}
+
+enum Sink {
+    Vorbis(Box<VorbisEncoder<BufWriter<File>>>),
+    Wav(WavWriter<BufWriter<File>>),
+}
+
+/// Converts `source_path` to `target_format` next to it, or into `output_dir`, trying the
+/// built-in encoder first and FFmpeg after. The audio is written to a hidden temporary file
+/// beside the target and only renamed over it once the conversion succeeded.
+pub fn convert_file(
+    source_path: &str,
+    target_format: &str,
+    output_dir: Option<&str>,
+    options: &EncodingOptions,
+    output: &OutputOptions,
+    batch: Option<&BatchOutputs>,
+    monitor: &Monitor,
+) -> Result<ConvertResult, String> {
+    log::info!("🔄 Converting {} to {} ({:?})", source_path, target_format.to_uppercase(), options);
+    options.validate()?;
+
+    let source = Path::new(source_path);
+    if !source.exists() {
+        return Err("Arquivo fonte não encontrado".to_string());
+    }
+
+    let stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
+    let extension = match target_format.to_lowercase().as_str() {
+        "ogg" | "vorbis" => "ogg",
+        "wav" => "wav",
+        _ => return Err(format!("Formato não suportado: {}", target_format)),
+    };
+
+    let wanted = match output_dir {
+        Some(dir) => Path::new(dir).join(format!("{}.{}", stem, extension)),
+        None => source.with_extension(extension),
+    };
+    if let Some(dir) = wanted.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Erro ao criar {}: {}", dir.display(), e))?;
+    }
+
+    let Some(target) = resolve_target(source, &wanted, output, batch)? else {
+        log::info!("⏭️ Skipping, {:?} already exists", wanted);
+        return Ok(ConvertResult {
+            success: true,
+            output_path: wanted.to_string_lossy().to_string(),
+            message: "Já existe - conversão ignorada".to_string(),
+            encoder: None,
+            loudness: None,
+            edit: None,
+            skipped: true,
+            backup_path: None,
+            output_info: None,
+        });
+    };
+
+    // Read before converting: with `Overwrite` the source may be the file being replaced.
+    let source_stream = probe_source(source);
+    let temp = create_temp(&target.path, extension)?;
+    let discard = || {
+        let _ = fs::remove_file(&temp);
+    };
+
+    let (encoder, applied) = match convert_native(source, &temp, extension, options, monitor) {
+        Ok(applied) => (Encoder::Native, applied),
+        Err(native_error) => {
+            let _ = fs::remove_file(&temp);
+            if (monitor.is_cancelled)() {
+                discard();
+                return Err(CANCELLED.to_string());
+            }
+            log::warn!("⚠️ Native conversion failed, trying FFmpeg: {}", native_error);
+            let applied = convert_with_ffmpeg(source, &temp, extension, options, monitor).map_err(|ffmpeg_error| {
+                discard();
+                if (monitor.is_cancelled)() {
+                    CANCELLED.to_string()
+                } else {
+                    format!("{}\n\nFFmpeg também falhou: {}", native_error, ffmpeg_error)
+                }
+            })?;
+            (Encoder::Ffmpeg, applied)
+        }
+    };
+
+    let expected = expected_output(&source_stream, options, applied.edit.as_ref());
+    let mut output_info = verify_output(&temp, extension, &expected).inspect_err(|_| discard())?;
+
+    let backup_path = match &output.backup_dir {
+        Some(dir) if !target.no_clobber && target.path.exists() => {
+            Some(backup(&target.path, dir).inspect_err(|_| discard())?)
+        }
+        _ => None,
+    };
+    move_into_place(&temp, &target.path, target.no_clobber).map_err(|e| {
+        discard();
+        if e.kind() == ErrorKind::AlreadyExists {
+            format!("{} foi criado por outro programa durante a conversão", target.path.display())
+        } else {
+            format!("Erro ao mover o arquivo convertido para {}: {}", target.path.display(), e)
+        }
+    })?;
+    if let Some(batch) = batch {
+        batch.record(&target.path);
+    }
+
+    // Verified under the temporary name; only the name-dependent fields change with the rename.
+    output_info.name = target.path.file_name().and_then(|n| n.to_str()).unwrap_or("unknown").to_string();
+    output_info.path = target.path.to_string_lossy().to_string();
+    output_info.relative_path = String::new();
+    let output_info = rules::recheck_name(output_info);
+
+    log::info!("✅ Conversion complete ({:?}): {:?}", encoder, target.path);
+    Ok(ConvertResult {
+        success: true,
+        output_path: target.path.to_string_lossy().to_string(),
+        message: format!("Convertido para {} com sucesso", extension.to_uppercase()),
+        encoder: Some(encoder),
+        loudness: applied.loudness,
+        edit: applied.edit,
+        skipped: false,
+        backup_path: backup_path.map(|p| p.to_string_lossy().to_string()),
+        output_info: Some(output_info),
+    })
+}
+
+/// Sample rate, channels and duration of the source's first audio track.
+#[derive(Debug, Default)]
+struct SourceStream {
+    sample_rate: Option<u32>,
+    channels: Option<usize>,
+    duration_secs: Option<f64>,
+}
+
+/// Reads what the container declares, asking ffprobe for whatever Symphonia could not tell.
+fn probe_source(source: &Path) -> SourceStream {
+    let declared = decode::stream_info(source).unwrap_or_default();
+    let duration_secs = match (declared.frames, declared.sample_rate) {
+        (Some(frames), Some(rate)) if rate > 0 => Some(frames as f64 / rate as f64),
+        _ => probe_duration_us(source).map(|us| us as f64 / 1_000_000.0),
+    };
+    SourceStream {
+        sample_rate: declared.sample_rate.or_else(|| probe_stream(source, "sample_rate")),
+        channels: declared.channels.or_else(|| probe_stream(source, "channels").map(|c| c as usize)),
+        duration_secs,
+    }
+}
+
+/// What the output should look like given the source and the options; `None` is not checked.
+fn expected_output(source: &SourceStream, options: &EncodingOptions, edit: Option<&EditReport>) -> SourceStream {
+    let channels = match options.channels {
+        ChannelPolicy::Keep => source.channels.map(|c| options.output_channels(c)),
+        _ => Some(options.output_channels(0)),
+    };
+    let duration_secs = match edit {
+        Some(report) if report.end_secs > report.start_secs => Some(report.end_secs - report.start_secs),
+        // Only a time trim without a known source duration leaves the end unknown.
+        Some(_) => None,
+        None => source.duration_secs,
+    };
+    SourceStream {
+        sample_rate: options.sample_rate.or(source.sample_rate),
+        channels,
+        duration_secs,
+    }
+}
+
+/// Checks the converted file with the scanner's analysis and rules, so a conversion never
+/// reports success for a file the scan would then flag.
+fn verify_output(output: &Path, extension: &str, expected: &SourceStream) -> Result<AudioFileInfo, String> {
+    let info = commands::verify_file(output);
+    let mut problems: Vec<String> = info
+        .violations
+        .iter()
+        .filter(|v| v.severity == Severity::Error)
+        .map(|v| v.message.clone())
+        .collect();
+
+    if extension == "ogg" && !info.is_vorbis {
+        problems.push(format!(
+            "sem cabeçalho Vorbis (codec: {})",
+            info.codec.as_deref().unwrap_or("desconhecido")
+        ));
+    }
+    if let Some(rate) = expected.sample_rate {
+        if info.sample_rate != Some(rate) {
+            problems.push(format!("taxa de amostragem {} Hz, esperado {} Hz", or_unknown(info.sample_rate), rate));
+        }
+    }
+    if let Some(channels) = expected.channels {
+        if info.channels.map(usize::from) != Some(channels) {
+            problems.push(format!("{} canais, esperado {}", or_unknown(info.channels), channels));
+        }
+    }
+    if let Some(duration) = expected.duration_secs {
+        match info.duration_secs {
+            Some(actual) if (actual - duration).abs() <= DURATION_TOLERANCE_SECS => {}
+            actual => problems.push(format!(
+                "duração {}s, esperado {:.3}s",
+                or_unknown(actual.map(|secs| format!("{:.3}", secs))),
+                duration
+            )),
+        }
+    }
+
+    if problems.is_empty() {
+        log::info!(
+            "🔍 Output verified: {:?} Hz, {:?} ch, {:?}s",
+            info.sample_rate,
+            info.channels,
+            info.duration_secs
+        );
+        Ok(info)
+    } else {
+        log::error!("❌ Output verification failed for {:?}: {:?}", output, problems);
+        Err(format!("O arquivo convertido não passou na verificação: {}", problems.join("; ")))
+    }
+}
+
+fn or_unknown<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "?".to_string(), |v| v.to_string())
+}
+
+/// Output names picked by conversions still running, so parallel jobs never choose the same
+/// one. Nothing is written at the final path until the conversion is done.
+static CLAIMED: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Outputs a batch has already written. Claims end with each job, so this is what stops a
+/// later job of the same batch from replacing an earlier one's output under `Overwrite`.
+#[derive(Default)]
+pub struct BatchOutputs(Mutex<Vec<PathBuf>>);
+
+impl BatchOutputs {
+    fn contains(&self, path: &Path) -> bool {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).iter().any(|p| p == path)
+    }
+
+    fn record(&self, path: &Path) {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).push(path.to_path_buf());
+    }
+}
+/// Makes every temporary file name unique within the process.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A claimed output name, released when dropped.
+struct Target {
+    path: PathBuf,
+    /// The file must not exist when the conversion is moved into place (`Suffix`, `Skip`).
+    no_clobber: bool,
+}
+
+impl Drop for Target {
+    fn drop(&mut self) {
+        let mut claimed = CLAIMED.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(i) = claimed.iter().position(|p| *p == self.path) {
+            claimed.swap_remove(i);
+        }
+    }
+}
+
+/// Picks where the output goes according to the collision policy, or `None` to skip.
+fn resolve_target(
+    source: &Path,
+    wanted: &Path,
+    output: &OutputOptions,
+    batch: Option<&BatchOutputs>,
+) -> Result<Option<Target>, String> {
+    let mut claimed = CLAIMED.lock().unwrap_or_else(|e| e.into_inner());
+    let free = |path: &Path| !path.exists() && !claimed.contains(&path.to_path_buf());
+
+    let path = match output.collision {
+        CollisionPolicy::Skip if !free(wanted) => return Ok(None),
+        CollisionPolicy::Skip => wanted.to_path_buf(),
+        CollisionPolicy::Overwrite => {
+            if claimed.iter().any(|p| p == wanted) || batch.is_some_and(|b| b.contains(wanted)) {
+                return Err(format!(
+                    "Outro arquivo desta conversão também gera {} - escolha criar \"nome (1)\" para manter os dois",
+                    wanted.display()
+                ));
+            }
+            if is_same_file(source, wanted) && output.backup_dir.is_none() {
+                return Err(
+                    "O arquivo convertido substituiria o original - escolha outra pasta de saída ou ative o backup"
+                        .to_string(),
+                );
+            }
+            wanted.to_path_buf()
+        }
+        CollisionPolicy::Suffix => (0..MAX_NAME_SUFFIX)
+            .map(|n| suffixed(wanted, n))
+            .find(|candidate| free(candidate))
+            .ok_or_else(|| format!("Nenhum nome livre para {}", wanted.display()))?,
+    };
+
+    claimed.push(path.clone());
+    Ok(Some(Target {
+        path,
+        no_clobber: output.collision != CollisionPolicy::Overwrite,
+    }))
+}
+
+/// `name.ext` for 0, then `name (1).ext`, `name (2).ext`...
+fn suffixed(path: &Path, n: u32) -> PathBuf {
+    if n == 0 {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
+    let name = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{} ({}).{}", stem, n, ext),
+        None => format!("{} ({})", stem, n),
+    };
+    path.with_file_name(name)
+}
+
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Creates an empty hidden file next to `target` for the conversion to write into, keeping
+/// the extension so FFmpeg still picks the right muxer.
+fn create_temp(target: &Path, extension: &str) -> Result<PathBuf, String> {
+    let stem = target.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
+    loop {
+        let n = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = target.with_file_name(format!(".{}.{}-{}.partial.{}", stem, std::process::id(), n, extension));
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(_) => return Ok(path),
+            // Left behind by an earlier run that had the same pid.
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Erro ao criar {}: {}", path.display(), e)),
+        }
+    }
+}
+
+/// Renames `temp` to `target`. With `no_clobber` it fails with `AlreadyExists` instead of
+/// replacing a file that appeared at `target` while converting.
+fn move_into_place(temp: &Path, target: &Path, no_clobber: bool) -> io::Result<()> {
+    if !no_clobber {
+        return fs::rename(temp, target);
+    }
+    match fs::hard_link(temp, target) {
+        Ok(()) => fs::remove_file(temp),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Err(e),
+        // Filesystems without hard links, like FAT or some network shares.
+        Err(_) if target.exists() => Err(ErrorKind::AlreadyExists.into()),
+        Err(_) => fs::rename(temp, target),
+    }
+}
+
+/// Copies `file` into `backup_dir` (relative to the file's own folder unless absolute).
+fn backup(file: &Path, backup_dir: &str) -> Result<PathBuf, String> {
+    let dir = match file.parent() {
+        Some(parent) => parent.join(backup_dir),
+        None => PathBuf::from(backup_dir),
+    };
+    fs::create_dir_all(&dir).map_err(|e| format!("Erro ao criar a pasta de backup {}: {}", dir.display(), e))?;
+
+    let name = file.file_name().ok_or_else(|| "Arquivo sem nome".to_string())?;
+    let destination = (0..MAX_NAME_SUFFIX)
+        .map(|n| suffixed(&dir.join(name), n))
+        .find(|p| !p.exists())
+        .ok_or_else(|| format!("Nenhum nome livre em {}", dir.display()))?;
+    fs::copy(file, &destination).map_err(|e| format!("Erro ao fazer backup de {}: {}", file.display(), e))?;
+    log::info!("🗄️ Backed up {:?} to {:?}", file, destination);
+    Ok(destination)
+}
+
+/// Decodes `source` and writes it to `output` as Vorbis (`ogg`) or 16-bit PCM (`wav`),
+/// applying the channel policy and resampling when `options` asks for another rate. Edits
+/// need a first pass to find the output length and edge silence; normalization measures
+/// the edited audio in another pass before scaling it in the last one.
+pub fn convert_native(
+    source: &Path,
+    output: &Path,
+    extension: &str,
+    options: &EncodingOptions,
+    monitor: &Monitor,
+) -> Result<Applied, String> {
+    let passes = 1 + options.edit.is_active() as usize + options.normalize.is_some() as usize;
+    let mut pass = 0;
+    let mut next_range = || {
+        pass += 1;
+        ((pass - 1) as f32 / passes as f32, pass as f32 / passes as f32)
+    };
+
+    let plan = if options.edit.is_active() {
+        let mut total = 0u64;
+        let mut out_rate = 0;
+        let mut silence = SilenceDetector::new(options.edit.silence_threshold_db());
+        decode_output(source, options, None, monitor, next_range(), |block, channels, rate| {
+            total += (block.len() / channels) as u64;
+            out_rate = rate;
+            silence.add_block(block, channels, rate);
+            Ok(())
+        })?;
+        let loop_region = loop_region(source, out_rate);
+        let plan = options.edit.plan(total, out_rate, &silence.finish(), loop_region)?;
+        log::info!("✂️ Edit plan: {:?}", plan.report);
+        Some(plan)
+    } else {
+        None
+    };
+
+    let normalization = match &options.normalize {
+        Some(target) => {
+            let mut meter = LoudnessMeter::default();
+            decode_output(source, options, plan.as_ref(), monitor, next_range(), |block, channels, rate| {
+                meter.add_block(block, channels, rate);
+                Ok(())
+            })?;
+            let before = meter.finish();
+            let gain_db = target.gain_db(&before);
+            log::info!("🔊 Loudness {:?} LUFS, applying {:+.2} dB", before.integrated_lufs, gain_db);
+            Some((target, before, gain_db))
+        }
+        None => None,
+    };
+    let gain = normalization.map(|(_, _, db)| 10f64.powf(db / 20.0) as f32);
+
+    let file = File::create(output).map_err(|e| format!("Erro ao criar {}: {}", output.display(), e))?;
+    let mut writer = Some(BufWriter::new(file));
+    let mut sink: Option<Sink> = None;
+    let mut after = LoudnessMeter::default();
+    let mut scaled = Vec::new();
+
+    decode_output(source, options, plan.as_ref(), monitor, next_range(), |block, channels, rate| {
+        if sink.is_none() {
+            sink = Some(open_sink(extension, writer.take(), rate, channels, options)?);
+        }
+        let block = match gain {
+            Some(gain) => {
+                scaled.clear();
+                scaled.extend(block.iter().map(|s| s * gain));
+                after.add_block(&scaled, channels, rate);
+                &scaled
+            }
+            None => block,
+        };
+        write_block(sink.as_mut(), block, channels).map_err(|e| format!("Erro ao codificar: {}", e))
+    })?;
+
+    match sink {
+        Some(Sink::Vorbis(encoder)) => encoder.finish().map(|_| ()).map_err(|e| e.to_string())?,
+        Some(Sink::Wav(writer)) => writer.finish().map(|_| ())?,
+        None => return Err("Nenhuma amostra de áudio decodificada".to_string()),
+    }
+
+    Ok(Applied {
+        loudness: normalization.map(|(target, before, gain_db)| LoudnessReport {
+            target_lufs: target.target_lufs,
+            true_peak_ceiling_dbtp: target.true_peak_dbtp,
+            before,
+            after: after.finish(),
+            gain_db,
+        }),
+        edit: plan.map(|p| p.report),
+    })
+}
+
+/// The source's loop tags converted to frames at `out_rate`.
+fn loop_region(source: &Path, out_rate: u32) -> Option<(u64, Option<u64>)> {
+    let info = commands::analyze_ogg_file(source);
+    let points = info.loop_points?;
+    let scale = out_rate as f64 / info.sample_rate.filter(|&r| r > 0)? as f64;
+    let frames = |samples: u64| (samples as f64 * scale).round() as u64;
+    Some((frames(points.start_sample), points.end_sample.map(frames)))
+}
+
+/// Decodes `source` and hands `on_block` interleaved blocks that already have the output
+/// channel layout, sample rate and edits, reporting progress within `progress_range`.
+fn decode_output(
+    source: &Path,
+    options: &EncodingOptions,
+    plan: Option<&EditPlan>,
+    monitor: &Monitor,
+    progress_range: (f32, f32),
+    mut on_block: impl FnMut(&[f32], usize, u32) -> Result<(), String>,
+) -> Result<(), String> {
+    let expected_frames = decode::declared_frames(source).filter(|&n| n > 0);
+    let mut frames_done = 0u64;
+    let mut resampler: Option<Resampler> = None;
+    let mut editor: Option<Editor> = None;
+    let mut output_format: Option<(usize, u32)> = None;
+    let mut failure: Option<String> = None;
+    let mut mixed = Vec::new();
+    let mut resampled = Vec::new();
+    let mut edited = Vec::new();
+
+    let summary = decode::decode_file_while(source, |samples, layout, rate| {
+        let channels = layout.count();
+        if (monitor.is_cancelled)() {
+            failure = Some(CANCELLED.to_string());
+            return false;
+        }
+        // An empty block would tell libvorbis the stream has ended.
+        if channels == 0 || samples.is_empty() {
+            return true;
+        }
+        let out_channels = options.output_channels(channels);
+        let out_rate = options.sample_rate.unwrap_or(rate);
+        if output_format.is_none() {
+            if out_rate != rate {
+                resampler = Some(Resampler::new(rate, out_rate, out_channels));
+            }
+            editor = plan.map(|&p| Editor::new(p, out_channels));
+            output_format = Some((out_channels, out_rate));
+        }
+
+        mix_channels(samples, layout, out_channels, &mut mixed);
+        let block = match resampler.as_mut() {
+            Some(r) => {
+                r.process(&mixed, &mut resampled);
+                &resampled
+            }
+            None => &mixed,
+        };
+        let block = match editor.as_mut() {
+            Some(e) => {
+                e.process(block, &mut edited);
+                &edited
+            }
+            None => block,
+        };
+        if !block.is_empty() {
+            if let Err(e) = on_block(block, out_channels, out_rate) {
+                failure = Some(e);
+                return false;
+            }
+        }
+
+        frames_done += (samples.len() / channels) as u64;
+        if let Some(total) = expected_frames {
+            let (start, end) = progress_range;
+            let done = (frames_done as f64 / total as f64).min(1.0) as f32;
+            (monitor.on_progress)(start + (end - start) * done);
+        }
+        true
+    })?;
+
+    if let Some(e) = failure {
+        return Err(e);
+    }
+    if let Some(first) = summary.errors.first() {
+        return Err(format!("Falha ao decodificar ({} erros): {}", summary.error_count, first));
+    }
+
+    if let (Some(r), Some((channels, rate))) = (resampler.as_mut(), output_format) {
+        r.flush(&mut resampled);
+        let block = match editor.as_mut() {
+            Some(e) => {
+                e.process(&resampled, &mut edited);
+                &edited
+            }
+            None => &resampled,
+        };
+        if !block.is_empty() {
+            on_block(block, channels, rate)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_block(sink: Option<&mut Sink>, samples: &[f32], channels: usize) -> Result<(), String> {
+    match sink {
+        Some(Sink::Vorbis(encoder)) => {
+            let planar: Vec<Vec<f32>> = (0..channels)
+                .map(|c| samples.iter().skip(c).step_by(channels).copied().collect())
+                .collect();
+            encoder.encode_audio_block(&planar).map_err(|e| e.to_string())
+        }
+        Some(Sink::Wav(writer)) => writer.write_samples(samples),
+        None => Ok(()),
+    }
+}
+
+fn open_sink(
+    extension: &str,
+    writer: Option<BufWriter<File>>,
+    rate: u32,
+    channels: usize,
+    options: &EncodingOptions,
+) -> Result<Sink, String> {
+    let writer = writer.ok_or_else(|| "Saída já em uso".to_string())?;
+    match extension {
+        "ogg" => {
+            let rate = NonZeroU32::new(rate).ok_or_else(|| "Taxa de amostragem desconhecida".to_string())?;
+            let channels = NonZeroU8::new(channels as u8).ok_or_else(|| "Sem canais de áudio".to_string())?;
+            let strategy = match options.max_bitrate_kbps.and_then(|kbps| NonZeroU32::new(kbps * 1000)) {
+                Some(maximum_bitrate) => VorbisBitrateManagementStrategy::ConstrainedAbr { maximum_bitrate },
+                None => VorbisBitrateManagementStrategy::QualityVbr {
+                    target_quality: options.quality,
+                },
+            };
+            let encoder = VorbisEncoderBuilder::new(rate, channels, writer)
+                .and_then(|mut builder| builder.bitrate_management_strategy(strategy).build())
+                .map_err(|e| format!("Erro ao iniciar codificador Vorbis: {}", e))?;
+            Ok(Sink::Vorbis(Box::new(encoder)))
+        }
+        "wav" => Ok(Sink::Wav(WavWriter::new(writer, rate, channels as u16)?)),
+        other => Err(format!("Formato não suportado: {}", other)),
+    }
+}
+
+/// Copies `samples` into `out` as `out_channels` interleaved channels. Surround input is
+/// folded to stereo by speaker position: centres and rears/sides at -3 dB to both or one
+/// side, LFE dropped, then scaled so a full-scale mix cannot clip. Mono output averages the
+/// stereo pair; mono input is duplicated for stereo output.
+fn mix_channels(samples: &[f32], layout: Channels, out_channels: usize, out: &mut Vec<f32>) {
+    let channels = layout.count();
+    out.clear();
+    if channels == out_channels {
+        out.extend_from_slice(samples);
+        return;
+    }
+    if channels == 1 {
+        for &s in samples {
+            out.extend(std::iter::repeat_n(s, out_channels));
+        }
+        return;
+    }
+    if channels == 2 && out_channels == 1 {
+        out.extend(samples.chunks_exact(2).map(|f| (f[0] + f[1]) * 0.5));
+        return;
+    }
+
+    // Interleaved samples follow the layout's bit order.
+    let gains: Vec<(f32, f32)> = layout.iter().map(stereo_gains).collect();
+    let (left_sum, right_sum) = gains.iter().fold((0.0, 0.0), |(l, r), &(gl, gr)| (l + gl, r + gr));
+    let scale = 1.0 / left_sum.max(right_sum).max(1.0);
+    for frame in samples.chunks_exact(channels) {
+        let (mut left, mut right) = (0.0, 0.0);
+        for (&s, &(gl, gr)) in frame.iter().zip(&gains) {
+            left += s * gl;
+            right += s * gr;
+        }
+        if out_channels == 1 {
+            out.push((left + right) * 0.5 * scale);
+        } else {
+            out.extend_from_slice(&[left * scale, right * scale]);
+        }
+    }
+}
+
+/// Left/right gains of one speaker position in a stereo fold-down.
+fn stereo_gains(position: Channels) -> (f32, f32) {
+    let left = Channels::REAR_LEFT
+        | Channels::SIDE_LEFT
+        | Channels::FRONT_LEFT_CENTRE
+        | Channels::REAR_LEFT_CENTRE
+        | Channels::FRONT_LEFT_WIDE
+        | Channels::FRONT_LEFT_HIGH
+        | Channels::TOP_FRONT_LEFT
+        | Channels::TOP_REAR_LEFT;
+    let right = Channels::REAR_RIGHT
+        | Channels::SIDE_RIGHT
+        | Channels::FRONT_RIGHT_CENTRE
+        | Channels::REAR_RIGHT_CENTRE
+        | Channels::FRONT_RIGHT_WIDE
+        | Channels::FRONT_RIGHT_HIGH
+        | Channels::TOP_FRONT_RIGHT
+        | Channels::TOP_REAR_RIGHT;
+
+    if position == Channels::FRONT_LEFT {
+        (1.0, 0.0)
+    } else if position == Channels::FRONT_RIGHT {
+        (0.0, 1.0)
+    } else if (Channels::LFE1 | Channels::LFE2).contains(position) {
+        (0.0, 0.0)
+    } else if left.contains(position) {
+        (FRAC_1_SQRT_2, 0.0)
+    } else if right.contains(position) {
+        (0.0, FRAC_1_SQRT_2)
+    } else {
+        // Centre speakers, and anything unknown, go to both sides.
+        (FRAC_1_SQRT_2, FRAC_1_SQRT_2)
+    }
+}
+
+/// The old conversion path, still used for codecs the built-in decoders do not cover.
+pub fn convert_with_ffmpeg(
+    source: &Path,
+    output: &Path,
+    extension: &str,
+    options: &EncodingOptions,
+    monitor: &Monitor,
+) -> Result<Applied, String> {
+    let ffmpeg = toolchain::ffmpeg()?;
+    if extension == "ogg" && !toolchain::status().libvorbis {
+        return Err("O FFmpeg encontrado não tem o encoder libvorbis - instale uma versão com suporte a Vorbis".to_string());
+    }
+    let duration_us = probe_duration_us(source);
+    let (edit_filters, edit) = ffmpeg_edit_filters(&options.edit, duration_us)?;
+    let channels = match options.channels {
+        ChannelPolicy::Keep => probe_stream(source, "channels").filter(|&c| c > 2).map(|_| 2),
+        ChannelPolicy::Mono => Some(1),
+        ChannelPolicy::Stereo => Some(2),
+    };
+    // Downmix and edit inside the filter graph so loudnorm measures what actually gets encoded.
+    let layout_filter =
+        channels.map(|c| format!("aformat=channel_layouts={}", if c == 1 { "mono" } else { "stereo" }));
+    let base_filters: Vec<String> = layout_filter.into_iter().chain(edit_filters).collect();
+
+    let (measured, progress_range) = match &options.normalize {
+        Some(target) => {
+            let mut filters = base_filters.clone();
+            filters.push(format!(
+                "loudnorm=I={}:TP={}:LRA=11:print_format=json",
+                target.target_lufs, target.true_peak_dbtp
+            ));
+            let mut cmd = ffmpeg_command(&ffmpeg, source);
+            cmd.arg("-af").arg(filters.join(",")).args(["-f", "null", "-"]);
+            let stderr = run_ffmpeg(cmd, duration_us, monitor, (0.0, 0.5))?;
+            let stats = parse_loudnorm_stats(&stderr)
+                .ok_or_else(|| "FFmpeg não retornou a medição de loudness".to_string())?;
+            // Silence measures as -inf LUFS, which loudnorm cannot take back as `measured_I`.
+            let input_i = stats.number("input_i")?;
+            let second_pass = if input_i.is_finite() {
+                Some(format!(
+                    "loudnorm=I={}:TP={}:LRA=11:measured_I={}:measured_TP={}:measured_LRA={}:measured_thresh={}:offset={}:linear=true:print_format=json",
+                    target.target_lufs,
+                    target.true_peak_dbtp,
+                    input_i,
+                    stats.number("input_tp")?,
+                    stats.number("input_lra")?,
+                    stats.number("input_thresh")?,
+                    stats.number("target_offset")?,
+                ))
+            } else {
+                log::info!("🔇 No measurable loudness, encoding without normalization");
+                None
+            };
+            (Some((target, stats, second_pass)), (0.5, 1.0))
+        }
+        None => (None, (0.0, 1.0)),
+    };
+
+    let mut cmd = ffmpeg_command(&ffmpeg, source);
+    match extension {
+        "ogg" => {
+            cmd.arg("-c:a").arg("libvorbis");
+            match options.max_bitrate_kbps {
+                Some(kbps) => cmd.arg("-b:a").arg(format!("{}k", kbps)).arg("-maxrate").arg(format!("{}k", kbps)),
+                None => cmd.arg("-q:a").arg(format!("{:.1}", options.quality * 10.0)),
+            };
+        }
+        "wav" => {
+            cmd.arg("-c:a").arg("pcm_s16le");
+        }
+        _ => {}
+    }
+
+    let mut filters = base_filters;
+    match &measured {
+        Some((_, _, Some(loudnorm))) => {
+            filters.push(loudnorm.clone());
+            // loudnorm always outputs 192 kHz.
+            let rate = options.sample_rate.or_else(|| probe_stream(source, "sample_rate")).unwrap_or(44_100);
+            filters.push(format!("aresample={}", rate));
+        }
+        _ => {
+            if let Some(rate) = options.sample_rate {
+                cmd.arg("-ar").arg(rate.to_string());
+            }
+        }
+    }
+    if !filters.is_empty() {
+        cmd.arg("-af").arg(filters.join(","));
+    }
+    cmd.arg(output);
+
+    let stderr = run_ffmpeg(cmd, duration_us, monitor, progress_range)?;
+
+    let loudness = measured.map(|(target, first, second_pass)| {
+        let before = first.stats("input_");
+        let after = match second_pass {
+            Some(_) => parse_loudnorm_stats(&stderr).map(|second| second.stats("output_")).unwrap_or_default(),
+            // Encoded as is, so the output measures like the input.
+            None => before,
+        };
+        let gain_db = match (before.integrated_lufs, after.integrated_lufs) {
+            (Some(b), Some(a)) => a - b,
+            _ => 0.0,
+        };
+        LoudnessReport {
+            target_lufs: target.target_lufs,
+            true_peak_ceiling_dbtp: target.true_peak_dbtp,
+            before,
+            after,
+            gain_db,
+        }
+    });
+    Ok(Applied { loudness, edit })
+}
+
+/// `atrim`/`afade` filters for the edits FFmpeg can do in a single pass. Silence trimming and
+/// the loop seam need the decoded audio up front, so only the native path offers them.
+fn ffmpeg_edit_filters(edit: &EditOptions, duration_us: Option<u64>) -> Result<(Vec<String>, Option<EditReport>), String> {
+    if !edit.is_active() {
+        return Ok((Vec::new(), None));
+    }
+    if matches!(edit.trim, Trim::Silence { .. }) || edit.loop_seam_ms > 0 {
+        return Err("Corte de silêncio e crossfade de loop só estão disponíveis na conversão nativa".to_string());
+    }
+
+    // Clamped to the source length the way `EditOptions::plan` clamps to the decoded frames,
+    // so the report matches what FFmpeg actually writes.
+    let duration = duration_us.map(|us| us as f64 / 1_000_000.0);
+    let clamp = |secs: f64| duration.map_or(secs, |d| secs.min(d));
+    let (start, end) = match edit.trim {
+        Trim::Time { start_secs, end_secs } => {
+            let end = end_secs.map(clamp).or(duration);
+            (end.map_or(start_secs, |e| start_secs.min(e)), end)
+        }
+        _ => (0.0, duration),
+    };
+    if end.is_some_and(|e| e <= start) {
+        return Err("Nada restou depois do corte".to_string());
+    }
+
+    let mut filters = Vec::new();
+    if let Trim::Time { end_secs, .. } = edit.trim {
+        let mut trim = format!("atrim=start={}", start);
+        if let (Some(_), Some(end)) = (end_secs, end) {
+            trim.push_str(&format!(":end={}", end));
+        }
+        filters.push(trim);
+        filters.push("asetpts=PTS-STARTPTS".to_string());
+    }
+    if edit.fade_in_ms > 0 {
+        filters.push(format!("afade=t=in:d={}", edit.fade_in_ms as f64 / 1000.0));
+    }
+    if edit.fade_out_ms > 0 {
+        let end = end.ok_or_else(|| "Duração desconhecida - não é possível aplicar o fade-out".to_string())?;
+        let fade = edit.fade_out_ms as f64 / 1000.0;
+        filters.push(format!("afade=t=out:st={}:d={}", (end - start - fade).max(0.0), fade));
+    }
+
+    let report = EditReport {
+        start_secs: start,
+        end_secs: end.unwrap_or(0.0),
+        seam_secs: 0.0,
+        used_loop_tags: false,
+    };
+    Ok((filters, Some(report)))
+}
+
+fn ffmpeg_command(ffmpeg: &Path, source: &Path) -> Command {
+    let mut cmd = Command::new(ffmpeg);
+    cmd.arg("-y")
+        .arg("-nostats")
+        .arg("-progress")
+        .arg("pipe:1")
+        .arg("-i")
+        .arg(source);
+    cmd
+}
+
+/// Runs `cmd`, mapping its progress into `progress_range`, and returns what it printed to stderr.
+fn run_ffmpeg(
+    mut cmd: Command,
+    duration_us: Option<u64>,
+    monitor: &Monitor,
+    progress_range: (f32, f32),
+) -> Result<String, String> {
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    log::debug!("Running: {:?}", cmd);
+
+    let mut child = cmd.spawn().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            toolchain::not_found()
+        } else {
+            format!("Erro ao executar FFmpeg: {}", e)
+        }
+    })?;
+
+    // stderr has to be drained alongside stdout or FFmpeg can block on a full pipe.
+    let stderr_reader = child.stderr.take().map(|mut stderr| {
+        std::thread::spawn(move || {
+            let mut text = String::new();
+            let _ = stderr.read_to_string(&mut text);
+            text
+        })
+    });
+
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if (monitor.is_cancelled)() {
+                let _ = child.kill();
+                break;
+            }
+            // `out_time_ms` is in microseconds despite its name; `out_time_us` is newer.
+            let out_time = line
+                .strip_prefix("out_time_us=")
+                .or_else(|| line.strip_prefix("out_time_ms="))
+                .and_then(|v| v.trim().parse::<u64>().ok());
+            if let (Some(done), Some(total)) = (out_time, duration_us) {
+                let (start, end) = progress_range;
+                let done = (done as f64 / total as f64).min(1.0) as f32;
+                (monitor.on_progress)(start + (end - start) * done);
+            }
+        }
+    }
+
+    let status = child.wait().map_err(|e| format!("Erro ao executar FFmpeg: {}", e))?;
+    let stderr = stderr_reader.and_then(|h| h.join().ok()).unwrap_or_default();
+
+    if (monitor.is_cancelled)() {
+        return Err(CANCELLED.to_string());
+    }
+    if !status.success() {
+        log::error!("❌ FFmpeg error: {}", stderr);
+        return Err(format!("Erro FFmpeg: {}", stderr));
+    }
+    Ok(stderr)
+}
+
+/// The JSON block `loudnorm=...:print_format=json` prints at the end of stderr. Every value
+/// is a string, `"-inf"` included.
+struct LoudnormStats(HashMap<String, String>);
+
+impl LoudnormStats {
+    /// A value as a number; `"-inf"` parses to negative infinity.
+    fn number(&self, key: &str) -> Result<f64, String> {
+        let value = self
+            .0
+            .get(key)
+            .ok_or_else(|| format!("A medição de loudness do FFmpeg não tem o valor {}", key))?;
+        value
+            .trim()
+            .parse()
+            .map_err(|_| format!("Valor inválido na medição de loudness do FFmpeg: {} = {}", key, value))
+    }
+
+    /// Reads the `input_*` or `output_*` group of values.
+    fn stats(&self, prefix: &str) -> LoudnessStats {
+        let value = |key: &str| self.number(&format!("{}{}", prefix, key)).ok().filter(|v| v.is_finite());
+        LoudnessStats {
+            integrated_lufs: value("i"),
+            loudness_range_lu: value("lra"),
+            true_peak_dbtp: value("tp"),
+            ..Default::default()
+        }
+    }
+}
+
+fn parse_loudnorm_stats(stderr: &str) -> Option<LoudnormStats> {
+    let start = stderr.rfind('{')?;
+    let end = start + stderr[start..].find('}')?;
+    serde_json::from_str(&stderr[start..=end]).ok().map(LoudnormStats)
+}
+
+fn probe_stream(source: &Path, entry: &str) -> Option<u32> {
+    let output = Command::new(toolchain::ffprobe()?)
+        .args(["-v", "error", "-select_streams", "a:0", "-show_entries"])
+        .arg(format!("stream={}", entry))
+        .args(["-of", "csv=p=0"])
+        .arg(source)
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+fn probe_duration_us(source: &Path) -> Option<u64> {
+    let output = Command::new(toolchain::ffprobe()?)
+        .args(["-v", "error", "-show_entries", "format=duration", "-of", "default=noprint_wrappers=1:nokey=1"])
+        .arg(source)
+        .output()
+        .ok()?;
+    let secs: f64 = String::from_utf8_lossy(&output.stdout).trim().parse().ok()?;
+    (secs > 0.0).then_some((secs * 1_000_000.0) as u64)
+}
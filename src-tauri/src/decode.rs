@@ -0,0 +1,161 @@
+use std::fs::File;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use symphonia::core::audio::{Channels, SampleBuffer};
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error;
+use symphonia::core::formats::{FormatOptions, FormatReader};
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// Corrupt streams can fail on every packet; past this only the count keeps growing.
+const MAX_REPORTED_ERRORS: usize = 10;
+
+#[derive(Debug, Clone, Default)]
+pub struct DecodeSummary {
+    pub sample_rate: u32,
+    pub channels: usize,
+    pub frames: u64,
+    pub error_count: usize,
+    pub errors: Vec<String>,
+}
+
+impl DecodeSummary {
+    fn record_error(&mut self, error: String) {
+        self.error_count += 1;
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(error);
+        }
+    }
+}
+
+/// Decodes the first audio track end to end, handing each block of interleaved samples
+/// to `on_block` along with its channel count and sample rate.
+pub fn decode_file<F>(path: &Path, mut on_block: F) -> Result<DecodeSummary, String>
+where
+    F: FnMut(&[f32], usize, u32),
+{
+    decode_file_while(path, |samples, layout, rate| {
+        on_block(samples, layout.count(), rate);
+        true
+    })
+}
+
+/// What the container declares about the first audio track, without decoding it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StreamInfo {
+    pub sample_rate: Option<u32>,
+    pub channels: Option<usize>,
+    pub frames: Option<u64>,
+}
+
+pub fn stream_info(path: &Path) -> Option<StreamInfo> {
+    let (format, track_id) = open(path).ok()?;
+    let params = &format.tracks().iter().find(|t| t.id == track_id)?.codec_params;
+    Some(StreamInfo {
+        sample_rate: params.sample_rate,
+        channels: params.channels.map(|c| c.count()),
+        frames: params.n_frames,
+    })
+}
+
+/// Number of frames the container declares for the first audio track, if any.
+pub fn declared_frames(path: &Path) -> Option<u64> {
+    stream_info(path)?.frames
+}
+
+/// Like `decode_file`, but hands over the channel layout instead of the count and stops as
+/// soon as `on_block` returns false.
+pub fn decode_file_while<F>(path: &Path, mut on_block: F) -> Result<DecodeSummary, String>
+where
+    F: FnMut(&[f32], Channels, u32) -> bool,
+{
+    let (mut format, track_id) = open(path)?;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.id == track_id)
+        .ok_or_else(|| "Nenhuma faixa de áudio encontrada".to_string())?;
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("Codec não suportado pelo decodificador: {}", e))?;
+
+    let mut summary = DecodeSummary {
+        sample_rate: track.codec_params.sample_rate.unwrap_or(0),
+        channels: track.codec_params.channels.map(|c| c.count()).unwrap_or(0),
+        ..Default::default()
+    };
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(Error::IoError(e)) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(e) => {
+                summary.record_error(e.to_string());
+                break;
+            }
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                let frames = decoded.frames();
+                if buffer.as_ref().is_none_or(|b| b.capacity() < decoded.capacity()) {
+                    buffer = Some(SampleBuffer::new(decoded.capacity() as u64, spec));
+                }
+                if let Some(buf) = buffer.as_mut() {
+                    buf.copy_interleaved_ref(decoded);
+                    summary.frames += frames as u64;
+                    summary.sample_rate = spec.rate;
+                    summary.channels = spec.channels.count();
+                    if !on_block(buf.samples(), spec.channels, summary.sample_rate) {
+                        break;
+                    }
+                }
+            }
+            Err(Error::DecodeError(e)) => {
+                summary.record_error(format!("pacote {}: {}", packet.ts(), e));
+            }
+            Err(e) => {
+                summary.record_error(e.to_string());
+                break;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+fn open(path: &Path) -> Result<(Box<dyn FormatReader>, u32), String> {
+    let file = File::open(path).map_err(|e| format!("Erro ao abrir: {}", e))?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let format_options = FormatOptions {
+        enable_gapless: true,
+        ..Default::default()
+    };
+    let probed = symphonia::default::get_probe()
+        .format(&hint, stream, &format_options, &MetadataOptions::default())
+        .map_err(|e| format!("Formato não suportado pelo decodificador: {}", e))?;
+
+    let track_id = probed
+        .format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .map(|t| t.id)
+        .ok_or_else(|| "Nenhuma faixa de áudio encontrada".to_string())?;
+    Ok((probed.format, track_id))
+}
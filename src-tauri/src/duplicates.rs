@@ -0,0 +1,70 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::AudioFileInfo;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateKind {
+    /// Byte-for-byte identical files.
+    Content,
+    /// Same decoded audio, different bytes (tags, muxing).
+    Audio,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DuplicateGroup {
+    pub kind: DuplicateKind,
+    pub hash: String,
+    /// Paths of the copies, largest first.
+    pub files: Vec<String>,
+    /// Bytes saved by keeping only the first file.
+    pub wasted_bytes: u64,
+}
+
+pub fn find_duplicates(files: &[AudioFileInfo]) -> Vec<DuplicateGroup> {
+    let mut groups = group_by(files, DuplicateKind::Content, |f| f.content_hash.as_deref());
+
+    // An audio group adds nothing when all its files are already one content group.
+    let audio_groups = group_by(files, DuplicateKind::Audio, |f| f.audio_fingerprint.as_deref());
+    for group in audio_groups {
+        let hashes: HashSet<_> = files
+            .iter()
+            .filter(|f| group.files.contains(&f.path))
+            .map(|f| f.content_hash.as_deref())
+            .collect();
+        if hashes.len() > 1 || hashes.contains(&None) {
+            groups.push(group);
+        }
+    }
+
+    groups.sort_by_key(|g| std::cmp::Reverse(g.wasted_bytes));
+    groups
+}
+
+fn group_by<'a, F>(files: &'a [AudioFileInfo], kind: DuplicateKind, hash_of: F) -> Vec<DuplicateGroup>
+where
+    F: Fn(&'a AudioFileInfo) -> Option<&'a str>,
+{
+    let mut by_hash: BTreeMap<&str, Vec<&AudioFileInfo>> = BTreeMap::new();
+    for file in files {
+        if let Some(hash) = hash_of(file) {
+            by_hash.entry(hash).or_default().push(file);
+        }
+    }
+
+    by_hash
+        .into_iter()
+        .filter(|(_, copies)| copies.len() > 1)
+        .map(|(hash, mut copies)| {
+            copies.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.path.cmp(&b.path)));
+            DuplicateGroup {
+                kind,
+                hash: hash.to_string(),
+                wasted_bytes: copies.iter().skip(1).map(|f| f.size_bytes).sum(),
+                files: copies.into_iter().map(|f| f.path.clone()).collect(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,293 @@
+use std::f32::consts::FRAC_PI_2;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{self, SilenceStats};
+
+/// Longest fade or loop crossfade accepted, in milliseconds.
+const MAX_EDIT_MS: u32 = 60_000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Trim {
+    #[default]
+    None,
+    Time {
+        start_secs: f64,
+        end_secs: Option<f64>,
+    },
+    /// Cuts leading and trailing audio quieter than `threshold_db`.
+    Silence { threshold_db: f64 },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(default)]
+pub struct EditOptions {
+    pub trim: Trim,
+    pub fade_in_ms: u32,
+    pub fade_out_ms: u32,
+    /// Crossfade that blends the end of the file into its start so `Looped` playback has no
+    /// seam; 0 turns it off. Loop tags, when present, choose the looped region.
+    pub loop_seam_ms: u32,
+}
+
+/// What the edits did, in seconds of the output timeline before trimming.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct EditReport {
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub seam_secs: f64,
+    pub used_loop_tags: bool,
+}
+
+impl EditOptions {
+    pub fn is_active(&self) -> bool {
+        self.trim != Trim::None || self.fade_in_ms > 0 || self.fade_out_ms > 0 || self.loop_seam_ms > 0
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Trim::Time { start_secs, end_secs } = self.trim {
+            if !start_secs.is_finite() || start_secs < 0.0 {
+                return Err(format!("Início do corte inválido: {}s", start_secs));
+            }
+            if end_secs.is_some_and(|end| !end.is_finite() || end <= start_secs) {
+                return Err("O fim do corte precisa vir depois do início".to_string());
+            }
+        }
+        if [self.fade_in_ms, self.fade_out_ms, self.loop_seam_ms].iter().any(|&ms| ms > MAX_EDIT_MS) {
+            return Err(format!("Fades e crossfade de loop vão até {} ms", MAX_EDIT_MS));
+        }
+        Ok(())
+    }
+
+    pub fn silence_threshold_db(&self) -> f64 {
+        match self.trim {
+            Trim::Silence { threshold_db } => threshold_db,
+            _ => analysis::DEFAULT_SILENCE_THRESHOLD_DB,
+        }
+    }
+
+    /// Turns the options into frame positions for an output of `total` frames at `rate`.
+    /// `loop_region` comes from the source's loop tags, already in output frames.
+    pub fn plan(
+        &self,
+        total: u64,
+        rate: u32,
+        silence: &SilenceStats,
+        loop_region: Option<(u64, Option<u64>)>,
+    ) -> Result<EditPlan, String> {
+        let frames = |secs: f64| ((secs * rate as f64).round() as u64).min(total);
+        let ms = |ms: u32| ms as u64 * rate as u64 / 1000;
+
+        let (mut start, mut end) = match self.trim {
+            Trim::None => (0, total),
+            Trim::Time { start_secs, end_secs } => (frames(start_secs), end_secs.map_or(total, frames)),
+            Trim::Silence { .. } => (frames(silence.leading_secs), total - frames(silence.trailing_secs)),
+        };
+
+        let seam_len = ms(self.loop_seam_ms);
+        let loop_region = loop_region.filter(|_| seam_len > 0);
+        if let Some((loop_start, loop_end)) = loop_region {
+            start = loop_start.min(total);
+            end = loop_end.map_or(total, |e| e.min(total));
+        }
+        if end <= start {
+            return Err("Nada restou depois do corte".to_string());
+        }
+
+        // With loop tags the audio before the loop start is what leads into it, so it makes
+        // the best material for the seam. Otherwise the head of the region is blended into
+        // the tail and the output starts right after it.
+        let seam_source = match loop_region {
+            Some(_) if start >= seam_len => start - seam_len,
+            _ => {
+                let head = start;
+                start += seam_len;
+                head
+            }
+        };
+        if seam_len > 0 && end < seam_source + 2 * seam_len {
+            return Err(format!("Trecho curto demais para um crossfade de loop de {} ms", self.loop_seam_ms));
+        }
+
+        let length = end - start;
+        Ok(EditPlan {
+            start,
+            end,
+            fade_in: ms(self.fade_in_ms).min(length),
+            fade_out: ms(self.fade_out_ms).min(length),
+            seam_source,
+            seam_len,
+            report: EditReport {
+                start_secs: start as f64 / rate as f64,
+                end_secs: end as f64 / rate as f64,
+                seam_secs: seam_len as f64 / rate as f64,
+                used_loop_tags: loop_region.is_some(),
+            },
+        })
+    }
+}
+
+/// Edits resolved to output frame positions.
+#[derive(Debug, Clone, Copy)]
+pub struct EditPlan {
+    /// First frame written.
+    pub start: u64,
+    /// One past the last frame written.
+    pub end: u64,
+    pub fade_in: u64,
+    pub fade_out: u64,
+    /// The `seam_len` frames starting here are crossfaded into the last `seam_len` frames.
+    pub seam_source: u64,
+    pub seam_len: u64,
+    pub report: EditReport,
+}
+
+/// Applies an `EditPlan` to interleaved blocks as they stream by.
+pub struct Editor {
+    plan: EditPlan,
+    channels: usize,
+    position: u64,
+    seam: Vec<f32>,
+}
+
+impl Editor {
+    pub fn new(plan: EditPlan, channels: usize) -> Self {
+        Editor {
+            plan,
+            channels,
+            position: 0,
+            seam: Vec::with_capacity(plan.seam_len as usize * channels),
+        }
+    }
+
+    pub fn process(&mut self, input: &[f32], out: &mut Vec<f32>) {
+        out.clear();
+        let plan = self.plan;
+        let seam_start = plan.end - plan.seam_len;
+
+        for frame in input.chunks_exact(self.channels) {
+            let p = self.position;
+            self.position += 1;
+
+            if (plan.seam_source..plan.seam_source + plan.seam_len).contains(&p) {
+                self.seam.extend_from_slice(frame);
+            }
+            if !(plan.start..plan.end).contains(&p) {
+                continue;
+            }
+
+            let mut gain = 1.0;
+            if p - plan.start < plan.fade_in {
+                gain *= (p - plan.start) as f32 / plan.fade_in as f32;
+            }
+            if plan.end - p <= plan.fade_out {
+                gain *= (plan.end - p - 1) as f32 / plan.fade_out as f32;
+            }
+
+            if plan.seam_len > 0 && p >= seam_start {
+                // Equal-power crossfade from the tail into the seam material.
+                let k = (p - seam_start) as usize;
+                let t = (k as f32 + 0.5) / plan.seam_len as f32;
+                let (fade_out, fade_in) = ((t * FRAC_PI_2).cos(), (t * FRAC_PI_2).sin());
+                let seam = &self.seam[k * self.channels..(k + 1) * self.channels];
+                out.extend(frame.iter().zip(seam).map(|(&s, &m)| (s * fade_out + m * fade_in) * gain));
+            } else {
+                out.extend(frame.iter().map(|&s| s * gain));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 1000;
+
+    fn plan(options: EditOptions, total: u64, loop_region: Option<(u64, Option<u64>)>) -> Result<EditPlan, String> {
+        options.plan(total, RATE, &SilenceStats::default(), loop_region)
+    }
+
+    #[test]
+    fn time_trim_is_clamped_to_the_source() {
+        let options = EditOptions {
+            trim: Trim::Time {
+                start_secs: 1.0,
+                end_secs: Some(60.0),
+            },
+            fade_out_ms: 10_000,
+            ..Default::default()
+        };
+        let plan = plan(options, 5000, None).unwrap();
+        assert_eq!((plan.start, plan.end), (1000, 5000));
+        assert_eq!(plan.fade_out, 4000);
+        assert_eq!(plan.report.end_secs, 5.0);
+    }
+
+    #[test]
+    fn trim_past_the_end_leaves_nothing() {
+        let options = EditOptions {
+            trim: Trim::Time {
+                start_secs: 6.0,
+                end_secs: None,
+            },
+            ..Default::default()
+        };
+        assert!(plan(options, 5000, None).is_err());
+    }
+
+    #[test]
+    fn silence_trim_cuts_both_ends() {
+        let options = EditOptions {
+            trim: Trim::Silence { threshold_db: -50.0 },
+            ..Default::default()
+        };
+        let silence = SilenceStats {
+            threshold_db: -50.0,
+            leading_secs: 0.5,
+            trailing_secs: 1.25,
+        };
+        let plan = options.plan(5000, RATE, &silence, None).unwrap();
+        assert_eq!((plan.start, plan.end), (500, 3750));
+    }
+
+    #[test]
+    fn loop_seam_without_tags_blends_the_head_into_the_tail() {
+        let options = EditOptions {
+            loop_seam_ms: 200,
+            ..Default::default()
+        };
+        let plan = plan(options, 5000, None).unwrap();
+        assert_eq!((plan.seam_source, plan.seam_len), (0, 200));
+        assert_eq!((plan.start, plan.end), (200, 5000));
+        assert!(!plan.report.used_loop_tags);
+    }
+
+    #[test]
+    fn loop_seam_with_tags_uses_the_lead_in() {
+        let options = EditOptions {
+            loop_seam_ms: 200,
+            ..Default::default()
+        };
+        let plan = plan(options, 5000, Some((1000, Some(4000)))).unwrap();
+        assert_eq!((plan.start, plan.end), (1000, 4000));
+        assert_eq!(plan.seam_source, 800);
+        assert!(plan.report.used_loop_tags);
+    }
+
+    #[test]
+    fn loop_tags_are_ignored_without_a_seam() {
+        let plan = plan(EditOptions::default(), 5000, Some((1000, Some(4000)))).unwrap();
+        assert_eq!((plan.start, plan.end), (0, 5000));
+    }
+
+    #[test]
+    fn loop_seam_needs_room_for_both_halves() {
+        let options = EditOptions {
+            loop_seam_ms: 300,
+            ..Default::default()
+        };
+        assert!(plan(options, 500, None).is_err());
+    }
+}
@@ -1,5 +1,20 @@
+mod analysis;
+mod cache;
 mod commands;
+mod convert;
+mod decode;
+mod duplicates;
+mod edit;
+mod resample;
+mod rules;
+mod sniff;
+mod summary;
+mod toolchain;
+mod vorbis;
+mod walk;
+mod wav;
 use commands::*;
+use tauri::Manager;
 
 #[tauri::command]
 fn open_in_explorer(path: String) -> Result<(), String> {
@@ -40,7 +55,7 @@ fn open_in_explorer(path: String) -> Result<(), String> {
                 return Ok(());
             }
         }
-        return Err("No file manager found".to_string());
+        Err("No file manager found".to_string())
     }
 
     #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
@@ -58,8 +73,19 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_process::init())
+        .setup(|app| {
+            if let Ok(dir) = app.path().app_data_dir() {
+                let settings = toolchain::ToolchainSettings::load(&dir.join(toolchain::SETTINGS_FILE));
+                toolchain::configure(settings.ffmpeg_path.map(std::path::PathBuf::from));
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             scan_audio_folder,
+            cancel_scan,
+            export_scan_summary,
+            list_scan_rules,
+            check_audio_entries,
             watch_assets_folder,
             save_project,
             load_project,
@@ -71,6 +97,13 @@ pub fn run() {
             export_to_folder,
             export_to_zip,
             convert_audio,
+            convert_batch,
+            list_encoding_presets,
+            load_encoding_settings,
+            save_encoding_settings,
+            cancel_conversion,
+            get_toolchain_status,
+            set_ffmpeg_path,
             open_in_explorer,
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,139 @@
+use std::f64::consts::PI;
+
+/// Zero crossings of the sinc kernel on each side of a sample at full bandwidth.
+const ZERO_CROSSINGS: usize = 16;
+
+/// Streaming windowed-sinc sample rate converter for interleaved `f32` blocks.
+pub struct Resampler {
+    channels: usize,
+    /// Input frames advanced per output frame.
+    step: f64,
+    /// Low-pass cutoff relative to the input Nyquist, below 1.0 when downsampling.
+    cutoff: f64,
+    half_width: usize,
+    buffer: Vec<f32>,
+    /// Position of the next output frame, in frames from the start of `buffer`.
+    position: f64,
+    input_frames: u64,
+    output_frames: u64,
+    from_rate: u32,
+    to_rate: u32,
+}
+
+impl Resampler {
+    pub fn new(from_rate: u32, to_rate: u32, channels: usize) -> Self {
+        let step = from_rate as f64 / to_rate as f64;
+        let cutoff = (1.0 / step).min(1.0);
+        let half_width = (ZERO_CROSSINGS as f64 / cutoff).ceil() as usize;
+        Resampler {
+            channels,
+            step,
+            cutoff,
+            half_width,
+            // Leading silence so the first output frame sees a full kernel.
+            buffer: vec![0.0; half_width * channels],
+            position: half_width as f64,
+            input_frames: 0,
+            output_frames: 0,
+            from_rate,
+            to_rate,
+        }
+    }
+
+    pub fn process(&mut self, input: &[f32], out: &mut Vec<f32>) {
+        out.clear();
+        self.buffer.extend_from_slice(input);
+        self.input_frames += (input.len() / self.channels) as u64;
+        self.drain(out, None);
+    }
+
+    /// Emits the frames still held back by the kernel's look-ahead.
+    pub fn flush(&mut self, out: &mut Vec<f32>) {
+        out.clear();
+        let expected = self.input_frames * self.to_rate as u64 / self.from_rate as u64;
+        self.buffer.extend(std::iter::repeat_n(0.0, self.half_width * self.channels));
+        self.drain(out, Some(expected));
+    }
+
+    fn drain(&mut self, out: &mut Vec<f32>, limit: Option<u64>) {
+        let buffered = self.buffer.len() / self.channels;
+        while (self.position.floor() as usize) + self.half_width < buffered {
+            if limit.is_some_and(|l| self.output_frames >= l) {
+                break;
+            }
+            let centre = self.position.floor() as usize;
+            let frac = self.position - centre as f64;
+            let start = centre + 1 - self.half_width;
+            for c in 0..self.channels {
+                let mut acc = 0.0f64;
+                for i in start..=centre + self.half_width {
+                    let x = i as f64 - centre as f64 - frac;
+                    acc += self.buffer[i * self.channels + c] as f64 * self.kernel(x);
+                }
+                out.push(acc as f32);
+            }
+            self.position += self.step;
+            self.output_frames += 1;
+        }
+
+        // Keep only what the next output frame's kernel still reaches.
+        let keep_from = (self.position.floor() as usize + 1).saturating_sub(self.half_width).min(buffered);
+        if keep_from > 0 {
+            self.buffer.drain(..keep_from * self.channels);
+            self.position -= keep_from as f64;
+        }
+    }
+
+    fn kernel(&self, x: f64) -> f64 {
+        let width = self.half_width as f64;
+        if x.abs() >= width {
+            return 0.0;
+        }
+        let t = x * self.cutoff;
+        let sinc = if t.abs() < 1e-9 { 1.0 } else { (PI * t).sin() / (PI * t) };
+        // Blackman window over the kernel span.
+        let w = 0.42 + 0.5 * (PI * x / width).cos() + 0.08 * (2.0 * PI * x / width).cos();
+        self.cutoff * sinc * w
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds `frames` of constant `level` in uneven blocks and returns everything written.
+    fn run(from: u32, to: u32, channels: usize, frames: usize, level: f32) -> Vec<f32> {
+        let mut resampler = Resampler::new(from, to, channels);
+        let input = vec![level; frames * channels];
+        let mut output = Vec::new();
+        let mut block = Vec::new();
+        for chunk in input.chunks(777 * channels) {
+            resampler.process(chunk, &mut block);
+            output.extend_from_slice(&block);
+        }
+        resampler.flush(&mut block);
+        output.extend_from_slice(&block);
+        output
+    }
+
+    #[test]
+    fn output_length_follows_the_rate_ratio() {
+        for (from, to, channels, frames) in [
+            (44100, 48000, 2, 44100),
+            (48000, 44100, 2, 48000),
+            (48000, 22050, 1, 12345),
+            (22050, 44100, 6, 1000),
+        ] {
+            let output = run(from, to, channels, frames, 0.0);
+            assert_eq!(output.len() % channels, 0);
+            assert_eq!((output.len() / channels) as u64, frames as u64 * to as u64 / from as u64);
+        }
+    }
+
+    #[test]
+    fn keeps_a_constant_signal_level() {
+        let output = run(44100, 48000, 2, 44100, 0.5);
+        let middle = &output[output.len() / 4..output.len() * 3 / 4];
+        assert!(middle.iter().all(|s| (s - 0.5).abs() < 0.01));
+    }
+}
@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+
+use crate::commands::AudioFileInfo;
+use crate::sniff;
+
+/// WAVs are loaded whole into memory and big Vorbis files slow down the first play.
+const MAX_FILE_BYTES: u64 = 50 * 1024 * 1024;
+const SUPPORTED_SAMPLE_RATES: [u32; 2] = [44100, 48000];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The game rejects or mis-plays the file.
+    Error,
+    /// Plays, but likely not as intended.
+    Warning,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RuleViolation {
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RuleInfo {
+    pub id: &'static str,
+    pub severity: Severity,
+    pub description: &'static str,
+}
+
+struct Rule {
+    id: &'static str,
+    severity: Severity,
+    description: &'static str,
+    check: fn(&AudioFileInfo) -> Option<String>,
+}
+
+/// Rules that depend only on the file name and not on its content.
+const NAME_RULES: &[&str] = &["extension_mismatch", "non_ascii_name"];
+
+const RULES: &[Rule] = &[
+    Rule {
+        id: "unsupported_format",
+        severity: Severity::Error,
+        description: "Apenas OGG Vorbis e WAV são aceitos pelo jogo",
+        check: unsupported_format,
+    },
+    Rule {
+        id: "extension_mismatch",
+        severity: Severity::Error,
+        description: "A extensão do arquivo deve corresponder ao conteúdo",
+        check: extension_mismatch,
+    },
+    Rule {
+        id: "vorbis_required",
+        severity: Severity::Error,
+        description: "Arquivos OGG devem conter um stream Vorbis",
+        check: vorbis_required,
+    },
+    Rule {
+        id: "wav_pcm_only",
+        severity: Severity::Error,
+        description: "WAV deve ser PCM simples de 8 ou 16 bits",
+        check: wav_pcm_only,
+    },
+    Rule {
+        id: "decode_errors",
+        severity: Severity::Error,
+        description: "O áudio deve decodificar sem erros e com a duração declarada",
+        check: decode_errors,
+    },
+    Rule {
+        id: "sample_rate",
+        severity: Severity::Warning,
+        description: "Taxa de amostragem deve ser 44.1 kHz ou 48 kHz",
+        check: sample_rate,
+    },
+    Rule {
+        id: "too_many_channels",
+        severity: Severity::Warning,
+        description: "Mais de 2 canais são mixados para estéreo pelo jogo",
+        check: too_many_channels,
+    },
+    Rule {
+        id: "file_too_large",
+        severity: Severity::Warning,
+        description: "Arquivos acima de 50 MB carregam devagar",
+        check: file_too_large,
+    },
+    Rule {
+        id: "non_ascii_name",
+        severity: Severity::Warning,
+        description: "Nomes com acentos ou símbolos podem falhar em alguns sistemas",
+        check: non_ascii_name,
+    },
+    Rule {
+        id: "clipping",
+        severity: Severity::Warning,
+        description: "O áudio não deve ter amostras clipadas",
+        check: clipping,
+    },
+];
+
+pub fn rule_list() -> Vec<RuleInfo> {
+    RULES
+        .iter()
+        .map(|r| RuleInfo {
+            id: r.id,
+            severity: r.severity,
+            description: r.description,
+        })
+        .collect()
+}
+
+pub fn evaluate(info: &AudioFileInfo, disabled: &[String]) -> Vec<RuleViolation> {
+    check(info, |rule| !disabled.iter().any(|d| d == rule.id))
+}
+
+/// Runs the enabled rules on a file that is valid if its analysis reported no error; any rule
+/// error makes it invalid and, if the analysis itself reported nothing, becomes its `error`.
+pub fn apply(mut info: AudioFileInfo, disabled: &[String]) -> AudioFileInfo {
+    info.is_valid_ogg = info.error.is_none();
+    info.violations = evaluate(&info, disabled);
+    mark_invalid(info)
+}
+
+/// Re-runs only the rules that look at the file name, for a file whose analysis is still
+/// valid but that was renamed afterwards.
+pub fn recheck_name(mut info: AudioFileInfo) -> AudioFileInfo {
+    let by_name = |id: &str| NAME_RULES.contains(&id);
+    info.violations.retain(|v| !by_name(&v.rule));
+    info.violations.extend(check(&info, |rule| by_name(rule.id)));
+    info.violations.sort_by_key(|v| RULES.iter().position(|rule| rule.id == v.rule));
+    mark_invalid(info)
+}
+
+fn check(info: &AudioFileInfo, include: impl Fn(&Rule) -> bool) -> Vec<RuleViolation> {
+    RULES
+        .iter()
+        .filter(|rule| include(rule))
+        .filter_map(|rule| {
+            (rule.check)(info).map(|message| RuleViolation {
+                rule: rule.id.to_string(),
+                severity: rule.severity,
+                message,
+            })
+        })
+        .collect()
+}
+
+fn mark_invalid(mut info: AudioFileInfo) -> AudioFileInfo {
+    if let Some(first) = info.violations.iter().find(|v| v.severity == Severity::Error) {
+        info.is_valid_ogg = false;
+        info.error = info.error.or_else(|| Some(first.message.clone()));
+    }
+    info
+}
+
+fn container(info: &AudioFileInfo) -> &str {
+    info.detected_format.as_deref().unwrap_or(&info.format)
+}
+
+fn unsupported_format(info: &AudioFileInfo) -> Option<String> {
+    match container(info) {
+        "OGG" | "WAV" => None,
+        other => Some(format!("Formato {} - converter para OGG Vorbis", other)),
+    }
+}
+
+fn extension_mismatch(info: &AudioFileInfo) -> Option<String> {
+    let detected = info.detected_format.as_deref()?;
+    (!sniff::extension_matches(&info.format, detected)).then(|| {
+        format!(
+            "Extensão .{} não corresponde ao conteúdo ({}) - converter para OGG Vorbis",
+            info.format.to_lowercase(),
+            detected
+        )
+    })
+}
+
+fn vorbis_required(info: &AudioFileInfo) -> Option<String> {
+    let codec = info.codec.as_deref()?;
+    (container(info) == "OGG" && !info.is_vorbis)
+        .then(|| format!("OGG {} - Stardew Valley requer OGG Vorbis!", codec))
+}
+
+fn wav_pcm_only(info: &AudioFileInfo) -> Option<String> {
+    if container(info) != "WAV" {
+        return None;
+    }
+    match info.codec.as_deref()? {
+        "PCM" => {}
+        "PCM (Extensible)" => {
+            return Some("WAV Extensible - o jogo pode rejeitar, converter para OGG Vorbis".to_string())
+        }
+        codec => return Some(format!("WAV {} - o jogo só aceita PCM, converter para OGG Vorbis", codec)),
+    }
+    match info.bits_per_sample {
+        Some(8 | 16) | None => None,
+        Some(bits) => Some(format!("WAV PCM {}-bit - o jogo só aceita 8 ou 16 bits", bits)),
+    }
+}
+
+fn decode_errors(info: &AudioFileInfo) -> Option<String> {
+    let check = info.decode_check.as_ref()?;
+    if let Some(first) = check.errors.first() {
+        Some(format!("Falha ao decodificar ({} erros): {}", check.error_count, first))
+    } else if check.length_matches == Some(false) {
+        Some(format!(
+            "Duração decodificada ({} amostras) difere do cabeçalho ({} amostras)",
+            check.decoded_samples,
+            info.total_samples.unwrap_or(0)
+        ))
+    } else {
+        None
+    }
+}
+
+fn sample_rate(info: &AudioFileInfo) -> Option<String> {
+    let rate = info.sample_rate?;
+    (!SUPPORTED_SAMPLE_RATES.contains(&rate))
+        .then(|| format!("Taxa de {} Hz - use 44100 ou 48000 Hz", rate))
+}
+
+fn too_many_channels(info: &AudioFileInfo) -> Option<String> {
+    let channels = info.channels?;
+    (channels > 2).then(|| format!("{} canais - use mono ou estéreo", channels))
+}
+
+fn file_too_large(info: &AudioFileInfo) -> Option<String> {
+    (info.size_bytes > MAX_FILE_BYTES)
+        .then(|| format!("Arquivo de {} - grande demais para streaming", info.size_display))
+}
+
+fn non_ascii_name(info: &AudioFileInfo) -> Option<String> {
+    let path = if info.relative_path.is_empty() { &info.name } else { &info.relative_path };
+    (!path.is_ascii()).then(|| format!("Nome com caracteres não-ASCII: {}", path))
+}
+
+fn clipping(info: &AudioFileInfo) -> Option<String> {
+    let clipped = info.loudness.as_ref()?.clipped_samples;
+    (clipped > 0).then(|| format!("{} amostras clipadas", clipped))
+}
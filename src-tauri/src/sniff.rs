@@ -0,0 +1,102 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+const SNIFF_LEN: u64 = 16;
+
+/// Container format from the file's leading bytes, named like `get_audio_format` names extensions.
+pub fn detect_format<R: Read + Seek>(reader: &mut R) -> io::Result<Option<&'static str>> {
+    reader.seek(SeekFrom::Start(0))?;
+    let header = read_header(reader)?;
+
+    if header.len() >= 10 && header.starts_with(b"ID3") {
+        let footer = if header[5] & 0x10 != 0 { 10 } else { 0 };
+        let tag_size = syncsafe_u32(&header[6..10]) as u64 + 10 + footer;
+        reader.seek(SeekFrom::Start(tag_size))?;
+        let inner = read_header(reader)?;
+        return Ok(Some(detect_format_from_bytes(&inner).unwrap_or("MP3")));
+    }
+
+    Ok(detect_format_from_bytes(&header))
+}
+
+pub fn detect_format_from_bytes(header: &[u8]) -> Option<&'static str> {
+    match header {
+        [b'O', b'g', b'g', b'S', ..] => Some("OGG"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => Some("WAV"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'R', b'M', b'P', b'3', ..] => Some("MP3"),
+        [b'f', b'L', b'a', b'C', ..] => Some("FLAC"),
+        [b'F', b'O', b'R', b'M', _, _, _, _, b'A', b'I', b'F', b'F' | b'C', ..] => Some("AIFF"),
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => Some("M4A"),
+        [0x30, 0x26, 0xB2, 0x75, 0x8E, 0x66, 0xCF, 0x11, ..] => Some("WMA"),
+        [b'M', b'A', b'C', b' ', ..] => Some("APE"),
+        [b'w', b'v', b'p', b'k', ..] => Some("WV"),
+        // ADTS shares the MPEG frame sync but always has layer bits 00.
+        [0xFF, b1, ..] if b1 & 0xF6 == 0xF0 => Some("AAC"),
+        [0xFF, b1, ..] if b1 & 0xE0 == 0xE0 && b1 & 0x06 != 0 => Some("MP3"),
+        _ => None,
+    }
+}
+
+/// Whether a file with extension `ext` (upper-case) may legitimately hold `detected`.
+pub fn extension_matches(ext: &str, detected: &str) -> bool {
+    match ext {
+        "OPUS" | "OGA" => detected == "OGG",
+        "AIF" | "AIFC" => detected == "AIFF",
+        "MP4" => detected == "M4A",
+        "AAC" => detected == "AAC" || detected == "M4A",
+        other => other == detected,
+    }
+}
+
+fn read_header<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut header = Vec::with_capacity(SNIFF_LEN as usize);
+    reader.take(SNIFF_LEN).read_to_end(&mut header)?;
+    Ok(header)
+}
+
+fn syncsafe_u32(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0, |acc, &b| (acc << 7) | (b & 0x7F) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn detects_containers_from_magic_bytes() {
+        let cases: [(&[u8], Option<&str>); 10] = [
+            (b"OggS\0\x02", Some("OGG")),
+            (b"RIFF\x24\0\0\0WAVEfmt ", Some("WAV")),
+            (b"RIFF\x24\0\0\0RMP3data", Some("MP3")),
+            (b"fLaC\0\0\0\x22", Some("FLAC")),
+            (b"FORM\0\0\0\x10AIFC", Some("AIFF")),
+            (b"\0\0\0\x20ftypM4A ", Some("M4A")),
+            (b"wvpk", Some("WV")),
+            (b"RIFF", None),
+            (b"hello world", None),
+            (b"", None),
+        ];
+        for (header, expected) in cases {
+            assert_eq!(detect_format_from_bytes(header), expected, "{:?}", header);
+        }
+    }
+
+    #[test]
+    fn tells_adts_from_mpeg_audio_frame_sync() {
+        assert_eq!(detect_format_from_bytes(&[0xFF, 0xF1, 0x50, 0x80]), Some("AAC"));
+        assert_eq!(detect_format_from_bytes(&[0xFF, 0xFB, 0x90, 0x64]), Some("MP3"));
+        assert_eq!(detect_format_from_bytes(&[0xFF, 0xE0, 0x00, 0x00]), None);
+    }
+
+    #[test]
+    fn looks_past_id3_tag() {
+        // Tag body of 0x81 bytes, written syncsafe as 0x01 0x01.
+        let mut file = b"ID3\x04\x00\x00\x00\x00\x01\x01".to_vec();
+        file.extend(std::iter::repeat_n(0, 0x81));
+        file.extend_from_slice(b"fLaC\0\0\0\x22");
+        assert_eq!(detect_format(&mut Cursor::new(file)).unwrap(), Some("FLAC"));
+
+        let bare = b"ID3\x04\x00\x00\x00\x00\x00\x00\xFF\xFB\x90\x64".to_vec();
+        assert_eq!(detect_format(&mut Cursor::new(bare)).unwrap(), Some("MP3"));
+    }
+}
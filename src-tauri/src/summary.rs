@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::AudioFileInfo;
+
+const LARGEST_FILES: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FileSize {
+    pub relative_path: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FolderSummary {
+    /// Folder relative to the scanned root; `.` is the root itself.
+    pub folder: String,
+    pub files: usize,
+    pub valid: usize,
+    pub total_bytes: u64,
+    pub total_duration_secs: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ScanSummary {
+    pub total_files: usize,
+    pub total_valid: usize,
+    pub total_bytes: u64,
+    pub total_duration_secs: f64,
+    /// Average over the files whose duration is known.
+    pub average_duration_secs: Option<f64>,
+    pub by_codec: BTreeMap<String, usize>,
+    pub by_sample_rate: BTreeMap<String, usize>,
+    pub by_channels: BTreeMap<String, usize>,
+    pub largest_files: Vec<FileSize>,
+    pub folders: Vec<FolderSummary>,
+}
+
+impl ScanSummary {
+    pub fn from_files(files: &[AudioFileInfo]) -> Self {
+        let mut summary = ScanSummary::default();
+        let mut folders: BTreeMap<String, FolderSummary> = BTreeMap::new();
+        let mut timed_files = 0;
+
+        for file in files {
+            let valid = is_valid(file);
+            let duration = file.duration_secs.unwrap_or(0.0);
+
+            summary.total_files += 1;
+            summary.total_valid += valid as usize;
+            summary.total_bytes += file.size_bytes;
+            summary.total_duration_secs += duration;
+            timed_files += file.duration_secs.is_some() as usize;
+
+            let codec = file
+                .codec
+                .clone()
+                .or_else(|| file.detected_format.clone())
+                .unwrap_or_else(|| "Desconhecido".to_string());
+            *summary.by_codec.entry(codec).or_default() += 1;
+
+            let rate = file
+                .sample_rate
+                .map_or_else(|| "Desconhecido".to_string(), |r| r.to_string());
+            *summary.by_sample_rate.entry(rate).or_default() += 1;
+
+            *summary.by_channels.entry(channel_layout(file.channels)).or_default() += 1;
+
+            let folder_name = match file.relative_path.rsplit_once('/') {
+                Some((folder, _)) => folder.to_string(),
+                None => ".".to_string(),
+            };
+            let folder = folders.entry(folder_name.clone()).or_insert_with(|| FolderSummary {
+                folder: folder_name,
+                ..Default::default()
+            });
+            folder.files += 1;
+            folder.valid += valid as usize;
+            folder.total_bytes += file.size_bytes;
+            folder.total_duration_secs += duration;
+        }
+
+        summary.average_duration_secs =
+            (timed_files > 0).then(|| summary.total_duration_secs / timed_files as f64);
+
+        let mut by_size: Vec<&AudioFileInfo> = files.iter().collect();
+        by_size.sort_by_key(|f| std::cmp::Reverse(f.size_bytes));
+        summary.largest_files = by_size
+            .into_iter()
+            .take(LARGEST_FILES)
+            .map(|f| FileSize {
+                relative_path: f.relative_path.clone(),
+                size_bytes: f.size_bytes,
+            })
+            .collect();
+
+        summary.folders = folders.into_values().collect();
+        summary
+    }
+
+    /// One row per figure, in `section,key,files,valid,bytes,duration_secs` columns.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("section,key,files,valid,bytes,duration_secs\n");
+        let mut row = |section: &str, key: &str, files: String, valid: String, bytes: String, secs: String| {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                section,
+                csv_field(key),
+                files,
+                valid,
+                bytes,
+                secs
+            ));
+        };
+
+        row(
+            "total",
+            "",
+            self.total_files.to_string(),
+            self.total_valid.to_string(),
+            self.total_bytes.to_string(),
+            format!("{:.3}", self.total_duration_secs),
+        );
+        if let Some(avg) = self.average_duration_secs {
+            row("average", "", String::new(), String::new(), String::new(), format!("{:.3}", avg));
+        }
+        for (section, counts) in [
+            ("codec", &self.by_codec),
+            ("sample_rate", &self.by_sample_rate),
+            ("channels", &self.by_channels),
+        ] {
+            for (key, count) in counts {
+                row(section, key, count.to_string(), String::new(), String::new(), String::new());
+            }
+        }
+        for folder in &self.folders {
+            row(
+                "folder",
+                &folder.folder,
+                folder.files.to_string(),
+                folder.valid.to_string(),
+                folder.total_bytes.to_string(),
+                format!("{:.3}", folder.total_duration_secs),
+            );
+        }
+        for file in &self.largest_files {
+            row(
+                "largest",
+                &file.relative_path,
+                String::new(),
+                String::new(),
+                file.size_bytes.to_string(),
+                String::new(),
+            );
+        }
+
+        csv
+    }
+}
+
+/// Parsed cleanly and broke no enabled error rule.
+pub fn is_valid(file: &AudioFileInfo) -> bool {
+    file.is_valid_ogg
+}
+
+fn channel_layout(channels: Option<u8>) -> String {
+    match channels {
+        Some(1) => "Mono".to_string(),
+        Some(2) => "Stereo".to_string(),
+        Some(6) => "5.1".to_string(),
+        Some(8) => "7.1".to_string(),
+        Some(n) => format!("{} canais", n),
+        None => "Desconhecido".to_string(),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,243 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+pub const SETTINGS_FILE: &str = "toolchain.json";
+
+/// Where a binary was found.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolSource {
+    Configured,
+    Path,
+    CommonDir,
+    /// Next to the app's own executable, where Tauri places bundled sidecars.
+    Sidecar,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ToolInfo {
+    pub path: String,
+    pub version: String,
+    pub source: ToolSource,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ToolchainStatus {
+    pub ffmpeg: Option<ToolInfo>,
+    pub ffprobe: Option<ToolInfo>,
+    /// Whether the FFmpeg found lists the `libvorbis` encoder.
+    pub libvorbis: bool,
+    pub configured_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ToolchainSettings {
+    /// FFmpeg binary, or the folder holding it, chosen by the user.
+    pub ffmpeg_path: Option<String>,
+}
+
+impl ToolchainSettings {
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| format!("Erro ao salvar configuração do FFmpeg: {}", e))
+    }
+}
+
+static CONFIGURED: Mutex<Option<PathBuf>> = Mutex::new(None);
+/// Probing runs a few processes, so it happens once and is reused until `refresh`.
+static STATUS: Mutex<Option<ToolchainStatus>> = Mutex::new(None);
+
+/// Sets the user's FFmpeg path and forgets the previous probe.
+pub fn configure(path: Option<PathBuf>) {
+    *CONFIGURED.lock().unwrap_or_else(|e| e.into_inner()) = path;
+    *STATUS.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+pub fn status() -> ToolchainStatus {
+    // Holding the lock while probing keeps parallel jobs from probing at the same time.
+    let mut cached = STATUS.lock().unwrap_or_else(|e| e.into_inner());
+    cached.get_or_insert_with(probe).clone()
+}
+
+/// Probes again, e.g. after FFmpeg was installed while the app was open.
+pub fn refresh() -> ToolchainStatus {
+    *STATUS.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    status()
+}
+
+pub fn ffmpeg() -> Result<PathBuf, String> {
+    status().ffmpeg.map(|tool| PathBuf::from(tool.path)).ok_or_else(not_found)
+}
+
+pub fn ffprobe() -> Option<PathBuf> {
+    status().ffprobe.map(|tool| PathBuf::from(tool.path))
+}
+
+pub fn not_found() -> String {
+    "FFmpeg não encontrado! Instale com:\n\n\
+    • CachyOS/Arch: sudo pacman -S ffmpeg\n\
+    • Ubuntu/Debian: sudo apt install ffmpeg\n\
+    • Fedora: sudo dnf install ffmpeg\n\
+    • macOS: brew install ffmpeg\n\
+    • Windows: winget install ffmpeg\n\n\
+    Ou indique onde ele está nas configurações do conversor."
+        .to_string()
+}
+
+fn probe() -> ToolchainStatus {
+    let configured = CONFIGURED.lock().unwrap_or_else(|e| e.into_inner()).clone();
+
+    let ffmpeg = find("ffmpeg", configured.as_deref());
+    // An ffprobe from the same install as the ffmpeg in use is preferred over the first one found.
+    let ffprobe = ffmpeg
+        .as_ref()
+        .and_then(|tool| {
+            let sibling = Path::new(&tool.path).with_file_name(executable("ffprobe"));
+            identify(&sibling, tool.source)
+        })
+        .or_else(|| find("ffprobe", configured.as_deref()));
+    let libvorbis = ffmpeg.as_ref().is_some_and(|tool| has_encoder(Path::new(&tool.path), "libvorbis"));
+
+    match &ffmpeg {
+        Some(tool) => log::info!(
+            "🧰 FFmpeg {} at {} ({:?}), libvorbis: {}, ffprobe: {}",
+            tool.version,
+            tool.path,
+            tool.source,
+            libvorbis,
+            ffprobe.is_some()
+        ),
+        None => log::info!("🧰 FFmpeg not found, only native conversion is available"),
+    }
+
+    ToolchainStatus {
+        ffmpeg,
+        ffprobe,
+        libvorbis,
+        configured_path: configured.map(|p| p.to_string_lossy().into_owned()),
+    }
+}
+
+fn find(name: &str, configured: Option<&Path>) -> Option<ToolInfo> {
+    candidates(name, configured)
+        .into_iter()
+        .filter(|(path, _)| path.is_file())
+        .find_map(|(path, source)| identify(&path, source))
+}
+
+/// Every place `name` may live, in the order they are tried.
+fn candidates(name: &str, configured: Option<&Path>) -> Vec<(PathBuf, ToolSource)> {
+    let file = executable(name);
+    let mut candidates = Vec::new();
+
+    if let Some(configured) = configured {
+        let path = if configured.is_dir() {
+            configured.join(&file)
+        } else if name == "ffmpeg" {
+            // The file the user picked, even when renamed (`ffmpeg-7.0`, `ffmpeg-static`).
+            configured.to_path_buf()
+        } else {
+            configured.with_file_name(&file)
+        };
+        candidates.push((path, ToolSource::Configured));
+    }
+    if let Some(paths) = env::var_os("PATH") {
+        candidates.extend(env::split_paths(&paths).map(|dir| (dir.join(&file), ToolSource::Path)));
+    }
+    candidates.extend(common_dirs().into_iter().map(|dir| (dir.join(&file), ToolSource::CommonDir)));
+    if let Some(dir) = env::current_exe().ok().and_then(|exe| exe.parent().map(Path::to_path_buf)) {
+        candidates.push((dir.join(&file), ToolSource::Sidecar));
+    }
+    candidates
+}
+
+/// Install locations package managers use that are often missing from the PATH a GUI app
+/// inherits, notably on macOS when launched from Finder.
+fn common_dirs() -> Vec<PathBuf> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE")).map(PathBuf::from);
+    let mut dirs = Vec::new();
+
+    #[cfg(target_os = "windows")]
+    {
+        for var in ["ProgramFiles", "ProgramFiles(x86)"] {
+            if let Some(dir) = env::var_os(var) {
+                dirs.push(PathBuf::from(dir).join("ffmpeg").join("bin"));
+            }
+        }
+        dirs.push(PathBuf::from(r"C:\ffmpeg\bin"));
+        dirs.push(PathBuf::from(r"C:\ProgramData\chocolatey\bin"));
+        if let Some(dir) = env::var_os("LOCALAPPDATA") {
+            dirs.push(PathBuf::from(dir).join("Microsoft").join("WinGet").join("Links"));
+        }
+        if let Some(home) = &home {
+            dirs.push(home.join("scoop").join("shims"));
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        dirs.extend(["/opt/homebrew/bin", "/usr/local/bin", "/opt/local/bin"].map(PathBuf::from));
+        if let Some(home) = &home {
+            dirs.push(home.join("bin"));
+        }
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        dirs.extend(["/usr/bin", "/usr/local/bin", "/snap/bin", "/opt/ffmpeg/bin"].map(PathBuf::from));
+        if let Some(home) = &home {
+            dirs.push(home.join(".local").join("bin"));
+        }
+    }
+
+    dirs
+}
+
+fn executable(name: &str) -> String {
+    format!("{}{}", name, env::consts::EXE_SUFFIX)
+}
+
+/// Runs `-version` to make sure the binary works and reads its version.
+fn identify(path: &Path, source: ToolSource) -> Option<ToolInfo> {
+    let output = Command::new(path).arg("-version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // "ffmpeg version 6.1.1-3ubuntu5 Copyright (c) 2000-2023 ..."
+    let text = String::from_utf8_lossy(&output.stdout);
+    let version = text.lines().next()?.split_whitespace().nth(2)?.to_string();
+    Some(ToolInfo {
+        path: path.to_string_lossy().into_owned(),
+        version,
+        source,
+    })
+}
+
+fn has_encoder(ffmpeg: &Path, encoder: &str) -> bool {
+    // " A....D libvorbis            libvorbis (codec vorbis)"
+    Command::new(ffmpeg)
+        .args(["-hide_banner", "-encoders"])
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .any(|line| line.split_whitespace().nth(1) == Some(encoder))
+        })
+        .unwrap_or(false)
+}
@@ -0,0 +1,388 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+
+/// 27-byte page header + 255 lacing values + 255 segments of 255 bytes.
+const MAX_PAGE_SIZE: u64 = 27 + 255 + 255 * 255;
+
+const CAPTURE_PATTERN: &[u8; 4] = b"OggS";
+
+const HEADER_TYPE_EOS: u8 = 0x04;
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u32) << 24;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04C1_1DB7
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// Ogg's CRC32 (polynomial 0x04C11DB7, no reflection), computed with the checksum field zeroed.
+fn page_crc(page: &[u8]) -> u32 {
+    page.iter().enumerate().fold(0u32, |crc, (i, &byte)| {
+        let byte = if (22..26).contains(&i) { 0 } else { byte };
+        (crc << 8) ^ CRC_TABLE[((crc >> 24) as u8 ^ byte) as usize]
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct PageError {
+    pub offset: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PageStats {
+    pub pages: u64,
+    pub streams: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct IdentificationHeader {
+    pub channels: u8,
+    pub sample_rate: u32,
+    pub bitrate_nominal: i32,
+}
+
+pub fn parse_identification_header(data: &[u8]) -> Option<IdentificationHeader> {
+    if data.len() < 30 || data[0] != 0x01 || &data[1..7] != b"vorbis" {
+        return None;
+    }
+
+    Some(IdentificationHeader {
+        channels: data[11],
+        sample_rate: u32::from_le_bytes([data[12], data[13], data[14], data[15]]),
+        bitrate_nominal: i32::from_le_bytes([data[20], data[21], data[22], data[23]]),
+    })
+}
+
+/// Codec of a logical stream, from the signature of its first (BOS) packet.
+pub fn identify_codec(first_packet: &[u8]) -> &'static str {
+    match first_packet {
+        [0x01, b'v', b'o', b'r', b'b', b'i', b's', ..] => "Vorbis",
+        [b'O', b'p', b'u', b's', b'H', b'e', b'a', b'd', ..] => "Opus",
+        [0x7F, b'F', b'L', b'A', b'C', ..] => "FLAC",
+        [b'S', b'p', b'e', b'e', b'x', ..] => "Speex",
+        [0x80, b't', b'h', b'e', b'o', b'r', b'a', ..] => "Theora",
+        [b'f', b'i', b's', b'h', b'e', b'a', b'd', 0, ..] => "Skeleton",
+        _ => "Desconhecido",
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CommentHeader {
+    pub vendor: String,
+    /// Field names are upper-cased, since Vorbis comment keys are case-insensitive.
+    pub tags: BTreeMap<String, Vec<String>>,
+}
+
+impl CommentHeader {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.tags
+            .get(&key.to_uppercase())
+            .and_then(|values| values.first())
+            .map(|v| v.as_str())
+    }
+
+    /// LOOPSTART and the loop end (from LOOPLENGTH, else LOOPEND) in samples.
+    pub fn loop_samples(&self, sample_rate: u32) -> Option<(u64, Option<u64>)> {
+        let start = parse_sample_position(self.get("LOOPSTART")?, sample_rate)?;
+        let end = match (self.get("LOOPLENGTH"), self.get("LOOPEND")) {
+            (Some(length), _) => parse_sample_position(length, sample_rate).map(|l| start + l),
+            (None, Some(end)) => parse_sample_position(end, sample_rate),
+            (None, None) => None,
+        };
+        Some((start, end))
+    }
+}
+
+/// Loop tags are usually sample counts, but some tools write `ss.fff` or `mm:ss.fff` instead.
+fn parse_sample_position(value: &str, sample_rate: u32) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(samples) = value.parse::<u64>() {
+        return Some(samples);
+    }
+
+    let secs = value
+        .split(':')
+        .try_fold(0.0, |acc, part| part.parse::<f64>().ok().map(|p| acc * 60.0 + p))?;
+    (secs.is_finite() && secs >= 0.0).then(|| (secs * sample_rate as f64).round() as u64)
+}
+
+pub fn parse_comment_header(data: &[u8]) -> Option<CommentHeader> {
+    if data.len() < 7 || data[0] != 0x03 || &data[1..7] != b"vorbis" {
+        return None;
+    }
+
+    let mut cursor = &data[7..];
+    let vendor = read_string(&mut cursor)?;
+    let count = read_u32(&mut cursor)?;
+
+    let mut tags: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for _ in 0..count {
+        let comment = read_string(&mut cursor)?;
+        if let Some((key, value)) = comment.split_once('=') {
+            tags.entry(key.to_uppercase()).or_default().push(value.to_string());
+        }
+    }
+
+    Some(CommentHeader { vendor, tags })
+}
+
+/// Reads packets up to the next one of `serial`, which for a Vorbis stream is its comment header.
+pub fn read_comment_header<R: Read + Seek>(
+    reader: &mut ogg::PacketReader<R>,
+    serial: u32,
+) -> Option<CommentHeader> {
+    loop {
+        match reader.read_packet() {
+            Ok(Some(packet)) if packet.stream_serial() == serial => {
+                return parse_comment_header(&packet.data);
+            }
+            Ok(Some(_)) => continue,
+            _ => return None,
+        }
+    }
+}
+
+fn read_u32(cursor: &mut &[u8]) -> Option<u32> {
+    let (bytes, rest) = cursor.split_first_chunk::<4>()?;
+    *cursor = rest;
+    Some(u32::from_le_bytes(*bytes))
+}
+
+fn read_string(cursor: &mut &[u8]) -> Option<String> {
+    let len = read_u32(cursor)? as usize;
+    if cursor.len() < len {
+        return None;
+    }
+    let (bytes, rest) = cursor.split_at(len);
+    *cursor = rest;
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// Granule position of the last page of `serial` that has one, read from the tail of the file.
+pub fn last_granule_position<R: Read + Seek>(reader: &mut R, serial: u32) -> io::Result<Option<u64>> {
+    let len = reader.seek(SeekFrom::End(0))?;
+    let start = len.saturating_sub(MAX_PAGE_SIZE * 2);
+    reader.seek(SeekFrom::Start(start))?;
+
+    let mut tail = Vec::with_capacity((len - start) as usize);
+    reader.take(len - start).read_to_end(&mut tail)?;
+
+    let mut pos = tail.len().saturating_sub(27);
+    loop {
+        if let Some(granule) = granule_at(&tail[pos..], serial) {
+            return Ok(Some(granule));
+        }
+        if pos == 0 {
+            return Ok(None);
+        }
+        pos -= 1;
+    }
+}
+
+fn granule_at(page: &[u8], serial: u32) -> Option<u64> {
+    if page.len() < 27 || &page[0..4] != CAPTURE_PATTERN || page[4] != 0 {
+        return None;
+    }
+
+    let segments = page[26] as usize;
+    if page.len() < 27 + segments {
+        return None;
+    }
+    let body_len: usize = page[27..27 + segments].iter().map(|&s| s as usize).sum();
+    if page.len() < 27 + segments + body_len {
+        return None;
+    }
+
+    let stored_crc = u32::from_le_bytes([page[22], page[23], page[24], page[25]]);
+    if page_crc(&page[..27 + segments + body_len]) != stored_crc {
+        return None;
+    }
+
+    let page_serial = u32::from_le_bytes([page[14], page[15], page[16], page[17]]);
+    let granule = i64::from_le_bytes([
+        page[6], page[7], page[8], page[9], page[10], page[11], page[12], page[13],
+    ]);
+
+    if page_serial != serial || granule < 0 {
+        return None;
+    }
+    Some(granule as u64)
+}
+
+/// Walks every page checking capture patterns, CRCs, per-stream sequence numbers and EOS flags.
+pub fn verify_pages<R: Read>(reader: R) -> Result<PageStats, PageError> {
+    let mut reader = BufReader::new(reader);
+    let mut streams: HashMap<u32, (u32, bool)> = HashMap::new();
+    let mut pages = 0u64;
+    let mut offset = 0u64;
+    let mut page = Vec::with_capacity(MAX_PAGE_SIZE as usize);
+
+    let error = |offset: u64, message: String| PageError { offset, message };
+
+    loop {
+        page.clear();
+        let read = append(&mut reader, 27, &mut page).map_err(|e| error(offset, e.to_string()))?;
+        if read == 0 {
+            break;
+        }
+        if read < 27 {
+            return Err(error(offset, "página truncada".to_string()));
+        }
+        if &page[0..4] != CAPTURE_PATTERN || page[4] != 0 {
+            return Err(error(offset, "cabeçalho de página inválido".to_string()));
+        }
+
+        let segments = page[26] as u64;
+        let read = append(&mut reader, segments, &mut page).map_err(|e| error(offset, e.to_string()))?;
+        let body_len: u64 = page[27..].iter().map(|&s| s as u64).sum();
+        let read = read
+            + append(&mut reader, body_len, &mut page).map_err(|e| error(offset, e.to_string()))?;
+        if read < segments + body_len {
+            return Err(error(offset, "página truncada".to_string()));
+        }
+
+        let stored_crc = u32::from_le_bytes([page[22], page[23], page[24], page[25]]);
+        if page_crc(&page) != stored_crc {
+            return Err(error(offset, "checksum CRC inválido".to_string()));
+        }
+
+        let serial = u32::from_le_bytes([page[14], page[15], page[16], page[17]]);
+        let sequence = u32::from_le_bytes([page[18], page[19], page[20], page[21]]);
+        let eos = page[5] & HEADER_TYPE_EOS != 0;
+
+        match streams.get_mut(&serial) {
+            Some((_, true)) => {
+                return Err(error(offset, "página após o fim do stream".to_string()));
+            }
+            Some((last, ended)) => {
+                if sequence != last.wrapping_add(1) {
+                    return Err(error(offset, format!("páginas perdidas ({} → {})", last, sequence)));
+                }
+                *last = sequence;
+                *ended = eos;
+            }
+            None => {
+                streams.insert(serial, (sequence, eos));
+            }
+        }
+
+        pages += 1;
+        offset += page.len() as u64;
+    }
+
+    if streams.is_empty() {
+        return Err(error(0, "nenhuma página Ogg".to_string()));
+    }
+    if streams.values().any(|(_, ended)| !ended) {
+        return Err(error(offset, "sem página de fim de stream (arquivo truncado?)".to_string()));
+    }
+
+    Ok(PageStats {
+        pages,
+        streams: streams.len(),
+    })
+}
+
+fn append<R: Read>(reader: &mut R, len: u64, buf: &mut Vec<u8>) -> io::Result<u64> {
+    reader.take(len).read_to_end(buf).map(|n| n as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER_TYPE_BOS: u8 = 0x02;
+
+    /// A single-segment page with a correct checksum.
+    fn page(serial: u32, sequence: u32, header_type: u8, body: &[u8]) -> Vec<u8> {
+        let mut page = Vec::new();
+        page.extend_from_slice(CAPTURE_PATTERN);
+        page.extend_from_slice(&[0, header_type]);
+        page.extend_from_slice(&0u64.to_le_bytes());
+        page.extend_from_slice(&serial.to_le_bytes());
+        page.extend_from_slice(&sequence.to_le_bytes());
+        page.extend_from_slice(&[0; 4]);
+        page.extend_from_slice(&[1, body.len() as u8]);
+        page.extend_from_slice(body);
+        let crc = page_crc(&page);
+        page[22..26].copy_from_slice(&crc.to_le_bytes());
+        page
+    }
+
+    fn stream() -> Vec<u8> {
+        [
+            page(7, 0, HEADER_TYPE_BOS, b"first"),
+            page(7, 1, 0, b"second"),
+            page(7, 2, HEADER_TYPE_EOS, b"third"),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn page_crc_matches_reference_value() {
+        // CRC-32/POSIX check value without its final inversion.
+        assert_eq!(page_crc(b"123456789"), !0x765E_7680);
+    }
+
+    #[test]
+    fn page_crc_ignores_stored_checksum() {
+        let mut page = page(1, 0, HEADER_TYPE_BOS, b"data");
+        let crc = page_crc(&page);
+        page[22..26].copy_from_slice(&[0xAA; 4]);
+        assert_eq!(page_crc(&page), crc);
+    }
+
+    #[test]
+    fn verify_pages_accepts_complete_stream() {
+        let stats = verify_pages(stream().as_slice()).unwrap();
+        assert_eq!(stats.pages, 3);
+        assert_eq!(stats.streams, 1);
+    }
+
+    #[test]
+    fn verify_pages_reports_crc_mismatch_offset() {
+        let mut data = stream();
+        let second = page(7, 0, HEADER_TYPE_BOS, b"first").len();
+        data[second + 28] ^= 0xFF;
+        let error = verify_pages(data.as_slice()).unwrap_err();
+        assert_eq!(error.offset, second as u64);
+        assert!(error.message.contains("CRC"), "{}", error.message);
+    }
+
+    #[test]
+    fn verify_pages_rejects_truncated_page() {
+        let data = stream();
+        let error = verify_pages(&data[..data.len() - 2]).unwrap_err();
+        assert!(error.message.contains("truncada"), "{}", error.message);
+    }
+
+    #[test]
+    fn verify_pages_rejects_missing_eos() {
+        let data = [page(7, 0, HEADER_TYPE_BOS, b"first"), page(7, 1, 0, b"second")].concat();
+        let error = verify_pages(data.as_slice()).unwrap_err();
+        assert!(error.message.contains("fim de stream"), "{}", error.message);
+    }
+
+    #[test]
+    fn verify_pages_rejects_skipped_sequence() {
+        let data = [page(7, 0, HEADER_TYPE_BOS, b"first"), page(7, 2, HEADER_TYPE_EOS, b"third")].concat();
+        let error = verify_pages(data.as_slice()).unwrap_err();
+        assert!(error.message.contains("0 → 2"), "{}", error.message);
+    }
+}
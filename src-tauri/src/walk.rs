@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SymlinkPolicy {
+    /// Follow links to files and folders; folder cycles are detected and skipped.
+    #[default]
+    Follow,
+    /// Follow links to files but never descend into linked folders.
+    FilesOnly,
+    /// Ignore symlinks entirely.
+    Skip,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct WalkOptions {
+    /// Globs matched against the path relative to the scanned folder; empty means everything.
+    pub include: Vec<String>,
+    /// Globs for files or folders to leave out; a matching folder is not descended into.
+    pub exclude: Vec<String>,
+    /// 1 scans only the folder itself, 2 its direct subfolders too, and so on.
+    pub max_depth: Option<usize>,
+    pub include_hidden: bool,
+    pub symlinks: SymlinkPolicy,
+}
+
+#[derive(Debug, Default)]
+pub struct WalkOutcome {
+    pub files: Vec<PathBuf>,
+    pub warnings: Vec<String>,
+}
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+/// Files under `root` accepted by `keep` and the walk options, in walk order.
+pub fn collect_files<F>(root: &Path, options: &WalkOptions, keep: F) -> Result<WalkOutcome, String>
+where
+    F: Fn(&Path) -> bool,
+{
+    let include = compile(&options.include)?;
+    let exclude = compile(&options.exclude)?;
+    let matches_any = |patterns: &[Pattern], rel: &str| patterns.iter().any(|p| p.matches_with(rel, MATCH_OPTIONS));
+
+    let mut walker = walkdir::WalkDir::new(root).follow_links(options.symlinks == SymlinkPolicy::Follow);
+    if let Some(depth) = options.max_depth {
+        walker = walker.max_depth(depth);
+    }
+
+    let mut outcome = WalkOutcome::default();
+    let mut seen = HashMap::new();
+    let canonical_root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+
+    let entries = walker.into_iter().filter_entry(|entry| {
+        if entry.depth() == 0 {
+            return true;
+        }
+        if !options.include_hidden && is_hidden(entry.file_name()) {
+            return false;
+        }
+        if options.symlinks == SymlinkPolicy::Skip && entry.path_is_symlink() {
+            return false;
+        }
+        !matches_any(&exclude, &relative(root, entry.path()))
+    });
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let path = e.path().map(|p| p.display().to_string()).unwrap_or_default();
+                let warning = match e.loop_ancestor() {
+                    Some(ancestor) => format!(
+                        "Loop de link simbólico ignorado: {} aponta para {}",
+                        path,
+                        ancestor.display()
+                    ),
+                    None => format!("Não foi possível ler {}: {}", path, e),
+                };
+                log::warn!("Walk error: {}", warning);
+                outcome.warnings.push(warning);
+                continue;
+            }
+        };
+
+        let path = entry.path();
+        if !path.is_file() || !keep(path) {
+            continue;
+        }
+        if !include.is_empty() && !matches_any(&include, &relative(root, path)) {
+            continue;
+        }
+        if options.symlinks == SymlinkPolicy::Skip {
+            outcome.files.push(entry.into_path());
+            continue;
+        }
+
+        // A link and its target, or two links to one folder, would otherwise list a file
+        // twice; keep the copy whose path has no links in it.
+        let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let is_real = canonical_root.join(relative(root, path)) == canonical;
+        match seen.get(&canonical) {
+            Some(&index) if is_real => outcome.files[index] = entry.into_path(),
+            Some(_) => {}
+            None => {
+                seen.insert(canonical, outcome.files.len());
+                outcome.files.push(entry.into_path());
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// `path` relative to `root` with forward slashes, as globs and asset paths expect.
+pub fn relative(root: &Path, path: &Path) -> String {
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn compile(patterns: &[String]) -> Result<Vec<Pattern>, String> {
+    patterns
+        .iter()
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| Pattern::new(p.trim()).map_err(|e| format!("Padrão inválido '{}': {}", p, e)))
+        .collect()
+}
+
+fn is_hidden(name: &std::ffi::OsStr) -> bool {
+    name.to_str().is_some_and(|n| n.starts_with('.'))
+}
@@ -0,0 +1,278 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+
+pub const WAVE_FORMAT_PCM: u16 = 0x0001;
+pub const WAVE_FORMAT_ADPCM: u16 = 0x0002;
+pub const WAVE_FORMAT_IEEE_FLOAT: u16 = 0x0003;
+pub const WAVE_FORMAT_ALAW: u16 = 0x0006;
+pub const WAVE_FORMAT_MULAW: u16 = 0x0007;
+pub const WAVE_FORMAT_IMA_ADPCM: u16 = 0x0011;
+pub const WAVE_FORMAT_MPEGLAYER3: u16 = 0x0055;
+pub const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+#[derive(Debug, Clone, Copy)]
+pub struct WavInfo {
+    pub format_tag: u16,
+    /// Format code from the SubFormat GUID of a WAVE_FORMAT_EXTENSIBLE header.
+    pub sub_format: Option<u16>,
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub block_align: u16,
+    pub bits_per_sample: u16,
+    pub data_bytes: u64,
+}
+
+impl WavInfo {
+    pub fn is_extensible(&self) -> bool {
+        self.format_tag == WAVE_FORMAT_EXTENSIBLE
+    }
+
+    pub fn effective_format(&self) -> u16 {
+        self.sub_format.unwrap_or(self.format_tag)
+    }
+
+    pub fn codec_name(&self) -> String {
+        let name = match self.effective_format() {
+            WAVE_FORMAT_PCM => "PCM".to_string(),
+            WAVE_FORMAT_ADPCM => "MS ADPCM".to_string(),
+            WAVE_FORMAT_IEEE_FLOAT => "IEEE Float".to_string(),
+            WAVE_FORMAT_ALAW => "A-law".to_string(),
+            WAVE_FORMAT_MULAW => "μ-law".to_string(),
+            WAVE_FORMAT_IMA_ADPCM => "IMA ADPCM".to_string(),
+            WAVE_FORMAT_MPEGLAYER3 => "MP3".to_string(),
+            other => format!("0x{:04X}", other),
+        };
+        if self.is_extensible() {
+            format!("{} (Extensible)", name)
+        } else {
+            name
+        }
+    }
+
+    pub fn frames(&self) -> Option<u64> {
+        (self.block_align > 0).then(|| self.data_bytes / self.block_align as u64)
+    }
+
+    pub fn duration_secs(&self) -> Option<f64> {
+        if self.sample_rate == 0 {
+            return None;
+        }
+        self.frames().map(|frames| frames as f64 / self.sample_rate as f64)
+    }
+}
+
+pub fn read_wav_info<R: Read + Seek>(reader: &mut R) -> Result<WavInfo, String> {
+    let file_len = reader.seek(SeekFrom::End(0)).map_err(|e| e.to_string())?;
+    reader.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+
+    let mut riff = [0u8; 12];
+    reader
+        .read_exact(&mut riff)
+        .map_err(|_| "Cabeçalho RIFF incompleto".to_string())?;
+    if &riff[0..4] != b"RIFF" || &riff[8..12] != b"WAVE" {
+        return Err("Não é um arquivo RIFF/WAVE".to_string());
+    }
+
+    let mut fmt: Option<WavInfo> = None;
+    let mut data_bytes: Option<u64> = None;
+    let mut pos = 12u64;
+
+    while fmt.is_none() || data_bytes.is_none() {
+        let mut header = [0u8; 8];
+        if reader.read_exact(&mut header).is_err() {
+            break;
+        }
+        let id = [header[0], header[1], header[2], header[3]];
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as u64;
+        pos += 8;
+
+        match &id {
+            b"fmt " => {
+                if size < 16 {
+                    return Err(format!("Chunk fmt muito pequeno ({} bytes)", size));
+                }
+                let mut buf = vec![0u8; size.min(64) as usize];
+                reader
+                    .read_exact(&mut buf)
+                    .map_err(|_| "Chunk fmt incompleto".to_string())?;
+                fmt = Some(parse_fmt_chunk(&buf));
+            }
+            b"data" => {
+                data_bytes = Some(size.min(file_len.saturating_sub(pos)));
+            }
+            _ => {}
+        }
+
+        pos += size + (size & 1);
+        reader.seek(SeekFrom::Start(pos)).map_err(|e| e.to_string())?;
+    }
+
+    let mut info = fmt.ok_or_else(|| "Chunk fmt não encontrado".to_string())?;
+    info.data_bytes = data_bytes.ok_or_else(|| "Chunk data não encontrado".to_string())?;
+    Ok(info)
+}
+
+fn parse_fmt_chunk(buf: &[u8]) -> WavInfo {
+    let u16_at = |i: usize| u16::from_le_bytes([buf[i], buf[i + 1]]);
+    let format_tag = u16_at(0);
+
+    let sub_format = if format_tag == WAVE_FORMAT_EXTENSIBLE && buf.len() >= 26 {
+        Some(u16_at(24))
+    } else {
+        None
+    };
+
+    WavInfo {
+        format_tag,
+        sub_format,
+        channels: u16_at(2),
+        sample_rate: u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]),
+        block_align: u16_at(12),
+        bits_per_sample: u16_at(14),
+        data_bytes: 0,
+    }
+}
+
+/// Writes 16-bit PCM WAV, the format the game's loader is happiest with.
+pub struct WavWriter<W: Write + Seek> {
+    sink: W,
+    data_bytes: u64,
+}
+
+impl<W: Write + Seek> WavWriter<W> {
+    pub fn new(mut sink: W, sample_rate: u32, channels: u16) -> Result<Self, String> {
+        let block_align = channels * 2;
+        let mut header = Vec::with_capacity(44);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&0u32.to_le_bytes());
+        header.extend_from_slice(b"WAVEfmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&WAVE_FORMAT_PCM.to_le_bytes());
+        header.extend_from_slice(&channels.to_le_bytes());
+        header.extend_from_slice(&sample_rate.to_le_bytes());
+        header.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+        header.extend_from_slice(&block_align.to_le_bytes());
+        header.extend_from_slice(&16u16.to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&0u32.to_le_bytes());
+        sink.write_all(&header).map_err(|e| e.to_string())?;
+
+        Ok(WavWriter { sink, data_bytes: 0 })
+    }
+
+    /// Appends interleaved samples in [-1, 1], clamping anything outside.
+    pub fn write_samples(&mut self, samples: &[f32]) -> Result<(), String> {
+        let bytes: Vec<u8> = samples
+            .iter()
+            .flat_map(|s| ((s.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16).to_le_bytes())
+            .collect();
+        self.sink.write_all(&bytes).map_err(|e| e.to_string())?;
+        self.data_bytes += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Fills in the chunk sizes left blank by `new`.
+    pub fn finish(mut self) -> Result<W, String> {
+        let data_bytes = u32::try_from(self.data_bytes)
+            .ok()
+            .filter(|n| n.checked_add(36).is_some())
+            .ok_or_else(|| "Áudio longo demais para WAV (limite de 4 GB)".to_string())?;
+
+        let patch = |sink: &mut W, offset: u64, value: u32| -> std::io::Result<()> {
+            sink.seek(SeekFrom::Start(offset))?;
+            sink.write_all(&value.to_le_bytes())
+        };
+        patch(&mut self.sink, 4, 36 + data_bytes).map_err(|e| e.to_string())?;
+        patch(&mut self.sink, 40, data_bytes).map_err(|e| e.to_string())?;
+        self.sink.flush().map_err(|e| e.to_string())?;
+        Ok(self.sink)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// A RIFF/WAVE file from `(id, body)` chunks, padding odd-sized bodies as the format requires.
+    fn riff(chunks: &[(&[u8; 4], Vec<u8>)]) -> Cursor<Vec<u8>> {
+        let mut body = b"WAVE".to_vec();
+        for (id, data) in chunks {
+            body.extend_from_slice(*id);
+            body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            body.extend_from_slice(data);
+            if data.len() % 2 == 1 {
+                body.push(0);
+            }
+        }
+        let mut file = b"RIFF".to_vec();
+        file.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        file.extend_from_slice(&body);
+        Cursor::new(file)
+    }
+
+    fn fmt(format_tag: u16, channels: u16, sample_rate: u32, bits: u16) -> Vec<u8> {
+        let block_align = channels * bits / 8;
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&format_tag.to_le_bytes());
+        fmt.extend_from_slice(&channels.to_le_bytes());
+        fmt.extend_from_slice(&sample_rate.to_le_bytes());
+        fmt.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+        fmt.extend_from_slice(&block_align.to_le_bytes());
+        fmt.extend_from_slice(&bits.to_le_bytes());
+        fmt
+    }
+
+    #[test]
+    fn reads_plain_pcm() {
+        let info = read_wav_info(&mut riff(&[(b"fmt ", fmt(WAVE_FORMAT_PCM, 2, 44100, 16)), (b"data", vec![0; 44100 * 4])]))
+            .unwrap();
+        assert_eq!(info.codec_name(), "PCM");
+        assert_eq!(info.channels, 2);
+        assert_eq!(info.frames(), Some(44100));
+        assert_eq!(info.duration_secs(), Some(1.0));
+    }
+
+    #[test]
+    fn skips_odd_sized_chunks() {
+        let info = read_wav_info(&mut riff(&[
+            (b"LIST", vec![1, 2, 3]),
+            (b"fmt ", fmt(WAVE_FORMAT_PCM, 1, 8000, 8)),
+            (b"junk", vec![9; 5]),
+            (b"data", vec![0; 800]),
+        ]))
+        .unwrap();
+        assert_eq!(info.sample_rate, 8000);
+        assert_eq!(info.frames(), Some(800));
+    }
+
+    #[test]
+    fn reads_extensible_sub_format() {
+        let mut ext = fmt(WAVE_FORMAT_EXTENSIBLE, 2, 48000, 32);
+        ext.extend_from_slice(&22u16.to_le_bytes());
+        ext.extend_from_slice(&32u16.to_le_bytes());
+        ext.extend_from_slice(&0x3u32.to_le_bytes());
+        // KSDATAFORMAT_SUBTYPE_IEEE_FLOAT: the format code followed by the fixed GUID tail.
+        ext.extend_from_slice(&[0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00]);
+        ext.extend_from_slice(&[0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71]);
+        let info = read_wav_info(&mut riff(&[(b"fmt ", ext), (b"data", vec![0; 64])])).unwrap();
+        assert!(info.is_extensible());
+        assert_eq!(info.effective_format(), WAVE_FORMAT_IEEE_FLOAT);
+        assert_eq!(info.codec_name(), "IEEE Float (Extensible)");
+        assert_eq!(info.frames(), Some(8));
+    }
+
+    #[test]
+    fn clamps_data_size_to_file_length() {
+        let mut file = riff(&[(b"fmt ", fmt(WAVE_FORMAT_PCM, 1, 44100, 16)), (b"data", vec![0; 100])]).into_inner();
+        let len = file.len();
+        file[len - 104..len - 100].copy_from_slice(&u32::MAX.to_le_bytes());
+        let info = read_wav_info(&mut Cursor::new(file)).unwrap();
+        assert_eq!(info.data_bytes, 100);
+    }
+
+    #[test]
+    fn rejects_missing_fmt_chunk() {
+        let error = read_wav_info(&mut riff(&[(b"data", vec![0; 4])])).unwrap_err();
+        assert!(error.contains("fmt"), "{}", error);
+    }
+}
@@ -181,6 +181,7 @@ export function App() {
   const { toast } = useApp();
   const { theme } = useTheme();
   const { loading, loadingMessage } = useAppState();
+  const tauri = useTauri();
 
   return (
     <motion.div 
@@ -197,7 +198,7 @@ export function App() {
       <SeasonalCorners />
       <JunimoMascot />
       
-      <LoadingOverlay visible={loading} message={loadingMessage} />
+      <LoadingOverlay visible={loading} message={loadingMessage} onCancel={tauri.isScanning ? tauri.cancelScan : undefined} />
       
       <motion.div 
         className={cn(
@@ -1,99 +1,82 @@
-import { memo, useCallback, useMemo } from 'react';
+import { memo, useCallback, useEffect, useState } from 'react';
 import { motion, AnimatePresence } from 'framer-motion';
-import { useAppState, useAppDispatch, useToastCtx } from '@/state/AppContext';
+import { useAppState, useAppDispatch, useTauri } from '@/state/AppContext';
 import { useTheme } from '@/contexts/ThemeContext';
 import { useLanguage } from '@/contexts/LanguageContext';
 import { cn } from '@/utils/cn';
-import type { ConvertJob } from '@/types/audio';
-
-function detectTauri(): boolean {
-  if (typeof window === 'undefined') return false;
-  const w = window as any;
-  return !!(
-    w.__TAURI__ || 
-    w.__TAURI_INTERNALS__ || 
-    w.__TAURI_IPC__ ||
-    (w.__TAURI_METADATA__ && w.__TAURI_METADATA__.currentWindow)
-  );
-}
-
-async function invoke<T>(cmd: string, args?: Record<string, unknown>): Promise<T> {
-  const w = window as any;
-  
-  if (w.__TAURI__?.core?.invoke) {
-    return w.__TAURI__.core.invoke(cmd, args);
-  }
-  if (w.__TAURI__?.invoke) {
-    return w.__TAURI__.invoke(cmd, args);
-  }
-  if (w.__TAURI_INTERNALS__?.invoke) {
-    return w.__TAURI_INTERNALS__.invoke(cmd, args);
-  }
-  
-  throw new Error('Tauri invoke not available');
-}
+import type { ChannelPolicy, CollisionPolicy, ConvertJob, EditOptions, EncodingOptions, EncodingPreset, ToolchainStatus, Trim } from '@/types/audio';
 
 export const AudioConverter = memo(function AudioConverter() {
-  const { convertJobs, scanResult, selectedScanFiles, assetsFolder } = useAppState();
+  const { convertJobs, scanResult, selectedScanFiles, assetsFolder, encodingOptions } = useAppState();
   const dispatch = useAppDispatch();
-  const { showToast } = useToastCtx();
+  const tauri = useTauri();
   const { theme } = useTheme();
   const { language } = useLanguage();
 
-  const isDesktop = useMemo(() => detectTauri(), []);
+  const isDesktop = tauri.isDesktop;
+  const [presets, setPresets] = useState<EncodingPreset[]>([]);
+  const [collision, setCollision] = useState<CollisionPolicy>('suffix');
+  const [keepBackup, setKeepBackup] = useState(false);
+  const [toolchain, setToolchain] = useState<ToolchainStatus | null>(null);
 
-  const convertFile = useCallback(async (filePath: string, fileName: string, targetFormat: 'ogg' | 'wav') => {
-    const jobId = `${Date.now()}-${fileName}`;
-    const job: ConvertJob = {
-      id: jobId,
-      sourceFile: fileName,
-      targetFormat,
-      status: 'converting',
-      progress: 0,
-    };
-    dispatch({ type: 'ADD_CONVERT_JOB', payload: job });
+  useEffect(() => {
+    tauri.listEncodingPresets().then(setPresets);
+  }, [tauri.listEncodingPresets]);
 
-    if (isDesktop) {
-      try {
-        const progressInterval = setInterval(() => {
-          dispatch({ type: 'UPDATE_CONVERT_JOB', payload: { id: jobId, updates: { progress: Math.min(90, (job.progress || 0) + 15) } } });
-        }, 300);
+  useEffect(() => {
+    tauri.getToolchainStatus().then(setToolchain);
+  }, [tauri.getToolchainStatus]);
 
-        const result = await invoke<{ success: boolean; output_path: string; error?: string }>('convert_audio', {
-          sourcePath: filePath,
-          targetFormat,
-        });
+  const updateToolchain = useCallback(async (action: Promise<ToolchainStatus | null>) => {
+    const status = await action;
+    if (status) setToolchain(status);
+  }, []);
 
-        clearInterval(progressInterval);
+  const activePreset = presets.find(p =>
+    p.options.quality === encodingOptions.quality &&
+    p.options.sample_rate === encodingOptions.sample_rate &&
+    p.options.channels === encodingOptions.channels &&
+    p.options.max_bitrate_kbps === encodingOptions.max_bitrate_kbps
+  );
 
-        if (result.success) {
-          dispatch({ type: 'UPDATE_CONVERT_JOB', payload: { id: jobId, updates: { status: 'done', progress: 100, outputPath: result.output_path } } });
-          showToast(`✅ ${fileName} → .${targetFormat}`, 'success');
-        } else {
-          dispatch({ type: 'UPDATE_CONVERT_JOB', payload: { id: jobId, updates: { status: 'error', error: result.error } } });
-          showToast(`❌ ${result.error}`, 'error');
-        }
-      } catch (err) {
-        dispatch({ type: 'UPDATE_CONVERT_JOB', payload: { id: jobId, updates: { status: 'error', error: String(err) } } });
-        showToast(`❌ ${err}`, 'error');
-      }
-    } else {
-      setTimeout(() => {
-        dispatch({ type: 'UPDATE_CONVERT_JOB', payload: { id: jobId, updates: { status: 'error', error: language === 'pt' ? 'Conversão só disponível na versão Desktop' : 'Conversion only available in Desktop version' } } });
-      }, 500);
-    }
-  }, [dispatch, showToast, language, isDesktop]);
+  const setOptions = useCallback((updates: Partial<EncodingOptions>) => {
+    dispatch({ type: 'SET_ENCODING_OPTIONS', payload: { ...encodingOptions, ...updates } });
+  }, [dispatch, encodingOptions]);
+
+  const setEdit = useCallback((updates: Partial<EditOptions>) => {
+    setOptions({ edit: { ...encodingOptions.edit, ...updates } });
+  }, [setOptions, encodingOptions.edit]);
+
+  const setTrimMode = useCallback((mode: Trim['mode']) => {
+    const trim: Trim = mode === 'time'
+      ? { mode, start_secs: 0, end_secs: null }
+      : mode === 'silence'
+        ? { mode, threshold_db: -60 }
+        : { mode };
+    setEdit({ trim });
+  }, [setEdit]);
 
   const convertSelected = useCallback(async (targetFormat: 'ogg' | 'wav') => {
     if (!scanResult || selectedScanFiles.length === 0) return;
 
-    for (const fileName of selectedScanFiles) {
-      const file = scanResult.files.find(f => f.name === fileName);
-      if (file) {
-        await convertFile(file.path, file.name, targetFormat);
-      }
+    const files = selectedScanFiles
+      .map(relativePath => scanResult.files.find(f => f.relative_path === relativePath))
+      .filter((f): f is NonNullable<typeof f> => !!f)
+      .map(f => ({ path: f.path, name: f.name }));
+
+    if (isDesktop) {
+      await tauri.convertBatch(files, targetFormat, { collision, backup_dir: collision === 'overwrite' && keepBackup ? 'backup' : null });
+      return;
     }
-  }, [scanResult, selectedScanFiles, convertFile]);
+
+    for (const file of files) {
+      const jobId = `${Date.now()}-${file.name}`;
+      dispatch({ type: 'ADD_CONVERT_JOB', payload: { id: jobId, sourceFile: file.name, targetFormat, status: 'pending', progress: 0 } });
+      setTimeout(() => {
+        dispatch({ type: 'UPDATE_CONVERT_JOB', payload: { id: jobId, updates: { status: 'error', error: language === 'pt' ? 'Conversão só disponível na versão Desktop' : 'Conversion only available in Desktop version' } } });
+      }, 500);
+    }
+  }, [scanResult, selectedScanFiles, isDesktop, tauri, dispatch, language, collision, keepBackup]);
 
   const clearJobs = useCallback(() => {
     dispatch({ type: 'CLEAR_CONVERT_JOBS' });
@@ -103,6 +86,7 @@ export const AudioConverter = memo(function AudioConverter() {
   const completedJobs = convertJobs.filter(j => j.status === 'done').length;
   const failedJobs = convertJobs.filter(j => j.status === 'error').length;
   const activeJobs = convertJobs.filter(j => j.status === 'converting').length;
+  const pendingJobs = convertJobs.filter(j => j.status === 'pending' || j.status === 'converting').length;
 
   return (
     <div className={cn(
@@ -130,17 +114,300 @@ export const AudioConverter = memo(function AudioConverter() {
       )}>
         {isDesktop
           ? (language === 'pt'
-            ? '🦀 Conversão nativa via Rust (FFmpeg/Symphonia). Selecione arquivos na lista abaixo para converter.'
-            : '🦀 Native conversion via Rust (FFmpeg/Symphonia). Select files from the list below to convert.')
+            ? '🦀 Conversão nativa via Rust (Symphonia + libvorbis, FFmpeg opcional). Selecione arquivos na lista abaixo para converter.'
+            : '🦀 Native conversion via Rust (Symphonia + libvorbis, FFmpeg optional). Select files from the list below to convert.')
           : (language === 'pt'
             ? '🌐 Conversão disponível apenas na versão Desktop. Na Web, use ferramentas externas como Audacity.'
             : '🌐 Conversion only available in Desktop version. On Web, use external tools like Audacity.')}
       </p>
 
+      {isDesktop && toolchain && (
+        <div className={cn('flex flex-wrap items-center gap-2 mb-3 text-xs',
+          theme === 'dark' ? 'text-gray-300' : 'text-gray-700'
+        )}>
+          <span title={toolchain.ffmpeg?.path}>
+            🧰 {toolchain.ffmpeg
+              ? `FFmpeg ${toolchain.ffmpeg.version}${toolchain.ffmpeg.source === 'configured' ? '' : ` (${toolchain.ffmpeg.source === 'sidecar'
+                ? (language === 'pt' ? 'embutido' : 'bundled')
+                : (language === 'pt' ? 'detectado' : 'detected')})`}`
+              : (language === 'pt' ? 'FFmpeg não encontrado - só conversão nativa' : 'FFmpeg not found - native conversion only')}
+          </span>
+          {toolchain.ffmpeg && (
+            <span className={toolchain.libvorbis ? '' : (theme === 'dark' ? 'text-amber-400' : 'text-amber-700')}>
+              {toolchain.libvorbis ? '✅ libvorbis' : (language === 'pt' ? '⚠️ sem libvorbis' : '⚠️ no libvorbis')}
+            </span>
+          )}
+          {toolchain.configured_path && toolchain.ffmpeg?.source !== 'configured' && (
+            <span className={theme === 'dark' ? 'text-amber-400' : 'text-amber-700'}>
+              ⚠️ {language === 'pt' ? 'Caminho configurado inválido' : 'Configured path not usable'}: {toolchain.configured_path}
+            </span>
+          )}
+          <button
+            onClick={() => updateToolchain(tauri.chooseFfmpegPath())}
+            className={cn('px-2 py-0.5 rounded border', theme === 'dark' ? 'border-gray-600 hover:bg-gray-700' : 'border-gray-300 hover:bg-gray-100')}
+          >
+            📂 {language === 'pt' ? 'Escolher FFmpeg' : 'Choose FFmpeg'}
+          </button>
+          {toolchain.configured_path && (
+            <button
+              onClick={() => updateToolchain(tauri.chooseFfmpegPath(true))}
+              className={cn('px-2 py-0.5 rounded border', theme === 'dark' ? 'border-gray-600 hover:bg-gray-700' : 'border-gray-300 hover:bg-gray-100')}
+            >
+              ✖ {language === 'pt' ? 'Busca automática' : 'Auto-detect'}
+            </button>
+          )}
+          <button
+            onClick={() => updateToolchain(tauri.getToolchainStatus(true))}
+            title={language === 'pt' ? 'Procurar de novo' : 'Search again'}
+            className={cn('px-2 py-0.5 rounded border', theme === 'dark' ? 'border-gray-600 hover:bg-gray-700' : 'border-gray-300 hover:bg-gray-100')}
+          >
+            🔄
+          </button>
+        </div>
+      )}
+
+      {isDesktop && (
+        <div className={cn('grid grid-cols-2 md:grid-cols-5 gap-2 mb-3 text-xs',
+          theme === 'dark' ? 'text-gray-300' : 'text-gray-700'
+        )}>
+          <label className="flex flex-col gap-1">
+            🎛️ {language === 'pt' ? 'Predefinição' : 'Preset'}
+            <select
+              value={activePreset?.id ?? ''}
+              onChange={(e) => {
+                const preset = presets.find(p => p.id === e.target.value);
+                if (preset) dispatch({ type: 'SET_ENCODING_OPTIONS', payload: { ...preset.options, normalize: encodingOptions.normalize, edit: encodingOptions.edit } });
+              }}
+              title={activePreset?.description}
+              className={cn('px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+            >
+              {!activePreset && <option value="">{language === 'pt' ? 'Personalizado' : 'Custom'}</option>}
+              {presets.map(p => <option key={p.id} value={p.id}>{p.name}</option>)}
+            </select>
+          </label>
+          <label className="flex flex-col gap-1">
+            {language === 'pt' ? 'Qualidade' : 'Quality'} ({Math.round(encodingOptions.quality * 10)})
+            <input
+              type="range"
+              min={-2}
+              max={10}
+              step={1}
+              value={Math.round(encodingOptions.quality * 10)}
+              disabled={encodingOptions.max_bitrate_kbps !== null}
+              onChange={(e) => setOptions({ quality: Number(e.target.value) / 10 })}
+              className="accent-purple-500 disabled:opacity-40"
+            />
+          </label>
+          <label className="flex flex-col gap-1">
+            {language === 'pt' ? 'Taxa de amostragem' : 'Sample rate'}
+            <select
+              value={encodingOptions.sample_rate ?? ''}
+              onChange={(e) => setOptions({ sample_rate: e.target.value ? Number(e.target.value) : null })}
+              className={cn('px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+            >
+              <option value="">{language === 'pt' ? 'Manter' : 'Keep'}</option>
+              {[22050, 32000, 44100, 48000].map(rate => <option key={rate} value={rate}>{rate} Hz</option>)}
+            </select>
+          </label>
+          <label className="flex flex-col gap-1">
+            {language === 'pt' ? 'Canais' : 'Channels'}
+            <select
+              value={encodingOptions.channels}
+              onChange={(e) => setOptions({ channels: e.target.value as ChannelPolicy })}
+              className={cn('px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+            >
+              <option value="keep">{language === 'pt' ? 'Manter' : 'Keep'}</option>
+              <option value="mono">Mono</option>
+              <option value="stereo">{language === 'pt' ? 'Estéreo' : 'Stereo'}</option>
+            </select>
+          </label>
+          <label className="flex flex-col gap-1">
+            {language === 'pt' ? 'Bitrate máximo' : 'Max bitrate'}
+            <select
+              value={encodingOptions.max_bitrate_kbps ?? ''}
+              onChange={(e) => setOptions({ max_bitrate_kbps: e.target.value ? Number(e.target.value) : null })}
+              className={cn('px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+            >
+              <option value="">{language === 'pt' ? 'Sem limite' : 'No limit'}</option>
+              {[64, 96, 128, 192, 256].map(kbps => <option key={kbps} value={kbps}>{kbps} kbps</option>)}
+            </select>
+          </label>
+          <div className="col-span-2 md:col-span-5 flex flex-wrap items-center gap-3">
+            <label className="flex items-center gap-2 cursor-pointer">
+              <input
+                type="checkbox"
+                checked={encodingOptions.normalize !== null}
+                onChange={(e) => setOptions({ normalize: e.target.checked ? { target_lufs: -16, true_peak_dbtp: -1 } : null })}
+                className="w-4 h-4 accent-purple-500"
+              />
+              🔊 {language === 'pt' ? 'Normalizar loudness' : 'Normalize loudness'}
+            </label>
+            {encodingOptions.normalize && (
+              <>
+                <label className="flex items-center gap-1">
+                  <input
+                    type="number"
+                    min={-40}
+                    max={-5}
+                    step={0.5}
+                    value={encodingOptions.normalize.target_lufs}
+                    onChange={(e) => setOptions({ normalize: { ...encodingOptions.normalize!, target_lufs: Number(e.target.value) } })}
+                    className={cn('w-16 px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+                  />
+                  LUFS
+                </label>
+                <label className="flex items-center gap-1">
+                  {language === 'pt' ? 'Teto' : 'Ceiling'}
+                  <input
+                    type="number"
+                    min={-9}
+                    max={0}
+                    step={0.1}
+                    value={encodingOptions.normalize.true_peak_dbtp}
+                    onChange={(e) => setOptions({ normalize: { ...encodingOptions.normalize!, true_peak_dbtp: Number(e.target.value) } })}
+                    className={cn('w-16 px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+                  />
+                  dBTP
+                </label>
+              </>
+            )}
+          </div>
+          <div className="col-span-2 md:col-span-5 flex flex-wrap items-center gap-3">
+            <label className="flex items-center gap-1">
+              ✂️ {language === 'pt' ? 'Corte' : 'Trim'}
+              <select
+                value={encodingOptions.edit.trim.mode}
+                onChange={(e) => setTrimMode(e.target.value as Trim['mode'])}
+                className={cn('px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+              >
+                <option value="none">{language === 'pt' ? 'Nenhum' : 'None'}</option>
+                <option value="time">{language === 'pt' ? 'Por tempo' : 'By time'}</option>
+                <option value="silence">{language === 'pt' ? 'Silêncio nas pontas' : 'Edge silence'}</option>
+              </select>
+            </label>
+            {encodingOptions.edit.trim.mode === 'time' && (
+              <>
+                <label className="flex items-center gap-1">
+                  {language === 'pt' ? 'de' : 'from'}
+                  <input
+                    type="number"
+                    min={0}
+                    step={0.1}
+                    value={encodingOptions.edit.trim.start_secs}
+                    onChange={(e) => setEdit({ trim: { mode: 'time', start_secs: Number(e.target.value), end_secs: encodingOptions.edit.trim.mode === 'time' ? encodingOptions.edit.trim.end_secs : null } })}
+                    className={cn('w-16 px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+                  />
+                  s
+                </label>
+                <label className="flex items-center gap-1">
+                  {language === 'pt' ? 'até' : 'to'}
+                  <input
+                    type="number"
+                    min={0}
+                    step={0.1}
+                    value={encodingOptions.edit.trim.end_secs ?? ''}
+                    placeholder={language === 'pt' ? 'fim' : 'end'}
+                    onChange={(e) => setEdit({ trim: { mode: 'time', start_secs: encodingOptions.edit.trim.mode === 'time' ? encodingOptions.edit.trim.start_secs : 0, end_secs: e.target.value ? Number(e.target.value) : null } })}
+                    className={cn('w-16 px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+                  />
+                  s
+                </label>
+              </>
+            )}
+            {encodingOptions.edit.trim.mode === 'silence' && (
+              <label className="flex items-center gap-1">
+                <input
+                  type="number"
+                  min={-90}
+                  max={-20}
+                  step={1}
+                  value={encodingOptions.edit.trim.threshold_db}
+                  onChange={(e) => setEdit({ trim: { mode: 'silence', threshold_db: Number(e.target.value) } })}
+                  className={cn('w-16 px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+                />
+                dBFS
+              </label>
+            )}
+            <label className="flex items-center gap-1">
+              Fade-in
+              <input
+                type="number"
+                min={0}
+                max={60000}
+                step={50}
+                value={encodingOptions.edit.fade_in_ms}
+                onChange={(e) => setEdit({ fade_in_ms: Number(e.target.value) })}
+                className={cn('w-20 px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+              />
+              ms
+            </label>
+            <label className="flex items-center gap-1">
+              Fade-out
+              <input
+                type="number"
+                min={0}
+                max={60000}
+                step={50}
+                value={encodingOptions.edit.fade_out_ms}
+                onChange={(e) => setEdit({ fade_out_ms: Number(e.target.value) })}
+                className={cn('w-20 px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+              />
+              ms
+            </label>
+            <label
+              className="flex items-center gap-1"
+              title={language === 'pt'
+                ? 'Funde o fim do arquivo no começo para loops sem emenda. Usa LOOPSTART/LOOPEND quando existirem.'
+                : 'Blends the end of the file into its start for seamless loops. Uses LOOPSTART/LOOPEND when present.'}
+            >
+              🔁 {language === 'pt' ? 'Crossfade de loop' : 'Loop crossfade'}
+              <input
+                type="number"
+                min={0}
+                max={60000}
+                step={50}
+                value={encodingOptions.edit.loop_seam_ms}
+                onChange={(e) => setEdit({ loop_seam_ms: Number(e.target.value) })}
+                className={cn('w-20 px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+              />
+              ms
+            </label>
+          </div>
+          <div className="col-span-2 md:col-span-5 flex flex-wrap items-center gap-3">
+            <label className="flex items-center gap-1">
+              📄 {language === 'pt' ? 'Se o arquivo já existir' : 'If the file exists'}
+              <select
+                value={collision}
+                onChange={(e) => setCollision(e.target.value as CollisionPolicy)}
+                className={cn('px-2 py-1 rounded border', theme === 'dark' ? 'bg-gray-800 border-gray-600 text-white' : 'bg-white border-gray-300 text-[#5c3d2e]')}
+              >
+                <option value="suffix">{language === 'pt' ? 'Criar "nome (1)"' : 'Create "name (1)"'}</option>
+                <option value="overwrite">{language === 'pt' ? 'Substituir' : 'Overwrite'}</option>
+                <option value="skip">{language === 'pt' ? 'Pular' : 'Skip'}</option>
+              </select>
+            </label>
+            <label
+              className="flex items-center gap-1"
+              title={language === 'pt'
+                ? 'Copia o arquivo substituído para a pasta "backup" ao lado dele'
+                : 'Copies the replaced file into a "backup" folder next to it'}
+            >
+              <input
+                type="checkbox"
+                checked={keepBackup}
+                disabled={collision !== 'overwrite'}
+                onChange={(e) => setKeepBackup(e.target.checked)}
+              />
+              🗄️ {language === 'pt' ? 'Manter backup' : 'Keep backup'}
+            </label>
+          </div>
+        </div>
+      )}
+
       <div className="flex flex-wrap gap-2 mb-3">
         <motion.button
           onClick={() => convertSelected('ogg')}
-          disabled={!hasSelectedFiles || activeJobs > 0}
+          disabled={!hasSelectedFiles || pendingJobs > 0}
           whileHover={{ scale: 1.02 }}
           whileTap={{ scale: 0.98 }}
           className={cn(
@@ -156,7 +423,7 @@ export const AudioConverter = memo(function AudioConverter() {
 
         <motion.button
           onClick={() => convertSelected('wav')}
-          disabled={!hasSelectedFiles || activeJobs > 0}
+          disabled={!hasSelectedFiles || pendingJobs > 0}
           whileHover={{ scale: 1.02 }}
           whileTap={{ scale: 0.98 }}
           className={cn(
@@ -170,7 +437,21 @@ export const AudioConverter = memo(function AudioConverter() {
           {hasSelectedFiles && <span className="px-1.5 py-0.5 bg-white/20 rounded text-xs">{selectedScanFiles.length}</span>}
         </motion.button>
 
-        {convertJobs.length > 0 && (
+        {pendingJobs > 0 && (
+          <motion.button
+            onClick={() => tauri.cancelConversion()}
+            whileHover={{ scale: 1.02 }}
+            whileTap={{ scale: 0.98 }}
+            className={cn(
+              'px-3 py-2 rounded-lg text-sm font-bold flex items-center gap-1',
+              theme === 'dark' ? 'bg-amber-700 text-white hover:bg-amber-600' : 'bg-amber-500 text-white hover:brightness-110'
+            )}
+          >
+            ⏹ {language === 'pt' ? 'Cancelar tudo' : 'Cancel all'}
+          </motion.button>
+        )}
+
+        {convertJobs.length > 0 && pendingJobs === 0 && (
           <motion.button
             onClick={clearJobs}
             whileHover={{ scale: 1.02 }}
@@ -202,7 +483,13 @@ export const AudioConverter = memo(function AudioConverter() {
             className="space-y-2 max-h-48 overflow-y-auto"
           >
             {convertJobs.map((job) => (
-              <ConvertJobRow key={job.id} job={job} theme={theme} onRemove={() => dispatch({ type: 'REMOVE_CONVERT_JOB', payload: job.id })} />
+              <ConvertJobRow
+                key={job.id}
+                job={job}
+                theme={theme}
+                onRemove={() => dispatch({ type: 'REMOVE_CONVERT_JOB', payload: job.id })}
+                onCancel={() => tauri.cancelConversion(job.id)}
+              />
             ))}
           </motion.div>
         )}
@@ -219,9 +506,13 @@ export const AudioConverter = memo(function AudioConverter() {
   );
 });
 
+function formatLufs(value: number | null): string {
+  return value === null ? '—' : value.toFixed(1);
+}
+
 const ConvertJobRow = memo(function ConvertJobRow({
-  job, theme, onRemove
-}: { job: ConvertJob; theme: 'light' | 'dark'; onRemove: () => void }) {
+  job, theme, onRemove, onCancel
+}: { job: ConvertJob; theme: 'light' | 'dark'; onRemove: () => void; onCancel: () => void }) {
   return (
     <motion.div
       initial={{ opacity: 0, x: -20 }}
@@ -240,6 +531,12 @@ const ConvertJobRow = memo(function ConvertJobRow({
         <motion.span animate={{ rotate: 360 }} transition={{ duration: 1, repeat: Infinity, ease: 'linear' }}>⏳</motion.span>
       ) : job.status === 'done' ? (
         <span>✅</span>
+      ) : job.status === 'pending' ? (
+        <span>🕒</span>
+      ) : job.status === 'skipped' ? (
+        <span>⏭️</span>
+      ) : job.status === 'cancelled' ? (
+        <span>⏹️</span>
       ) : (
         <span>❌</span>
       )}
@@ -252,7 +549,39 @@ const ConvertJobRow = memo(function ConvertJobRow({
           <div className={cn('text-xs truncate', theme === 'dark' ? 'text-red-400' : 'text-red-600')}>{job.error}</div>
         )}
         {job.outputPath && (
-          <div className={cn('text-xs truncate', theme === 'dark' ? 'text-green-400' : 'text-green-600')}>📁 {job.outputPath}</div>
+          <div className={cn('text-xs truncate', theme === 'dark' ? 'text-green-400' : 'text-green-600')}>
+            {job.status === 'skipped' ? '⏭️' : '📁'} {job.outputPath}
+          </div>
+        )}
+        {job.outputInfo && (
+          <div
+            className={cn('text-xs truncate', theme === 'dark' ? 'text-gray-300' : 'text-gray-600')}
+            title={job.outputInfo.violations.map(v => v.message).join('\n') || undefined}
+          >
+            🔍 {job.outputInfo.codec ?? job.outputInfo.detected_format}
+            {job.outputInfo.sample_rate !== null && ` · ${(job.outputInfo.sample_rate / 1000).toFixed(1)} kHz`}
+            {job.outputInfo.channels !== null && ` · ${job.outputInfo.channels} ch`}
+            {job.outputInfo.duration_secs !== null && ` · ${job.outputInfo.duration_secs.toFixed(2)}s`}
+            {` · ${job.outputInfo.size_display}`}
+            {job.outputInfo.violations.length > 0 && ` · ⚠️ ${job.outputInfo.violations.length}`}
+          </div>
+        )}
+        {job.backupPath && (
+          <div className={cn('text-xs truncate', theme === 'dark' ? 'text-gray-400' : 'text-gray-500')}>🗄️ {job.backupPath}</div>
+        )}
+        {job.edit && (
+          <div className={cn('text-xs truncate', theme === 'dark' ? 'text-blue-300' : 'text-blue-700')}>
+            ✂️ {job.edit.start_secs.toFixed(2)}s – {job.edit.end_secs.toFixed(2)}s
+            {job.edit.seam_secs > 0 && ` · 🔁 ${Math.round(job.edit.seam_secs * 1000)} ms`}
+            {job.edit.used_loop_tags && ' · LOOPSTART/LOOPEND'}
+          </div>
+        )}
+        {job.loudness && (
+          <div className={cn('text-xs truncate', theme === 'dark' ? 'text-purple-300' : 'text-purple-700')}>
+            🔊 {formatLufs(job.loudness.before.integrated_lufs)} → {formatLufs(job.loudness.after.integrated_lufs)} LUFS
+            {' · '}{job.loudness.gain_db >= 0 ? '+' : ''}{job.loudness.gain_db.toFixed(1)} dB
+            {job.loudness.after.true_peak_dbtp !== null && ` · ${job.loudness.after.true_peak_dbtp.toFixed(1)} dBTP`}
+          </div>
         )}
       </div>
 
@@ -266,7 +595,18 @@ const ConvertJobRow = memo(function ConvertJobRow({
         </div>
       )}
 
-      {job.status !== 'converting' && (
+      {(job.status === 'pending' || job.status === 'converting') ? (
+        <motion.button
+          onClick={onCancel}
+          whileHover={{ scale: 1.1 }}
+          whileTap={{ scale: 0.9 }}
+          className={cn('w-6 h-6 rounded-full flex items-center justify-center text-xs',
+            theme === 'dark' ? 'bg-gray-700 text-gray-400 hover:bg-amber-600 hover:text-white' : 'bg-gray-200 text-gray-500 hover:bg-amber-500 hover:text-white'
+          )}
+        >
+          ⏹
+        </motion.button>
+      ) : (
         <motion.button
           onClick={onRemove}
           whileHover={{ scale: 1.1 }}
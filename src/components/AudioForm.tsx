@@ -200,7 +200,7 @@ export function AudioForm({ onAdd, existingIds, showToast, scannedFiles = [] }:
             </label>
             <div className="space-y-1 max-h-48 overflow-y-auto">
               {scannedFiles.map((sf, i) => {
-                const isAlreadyAdded = files.includes(sf.name);
+                const isAlreadyAdded = files.includes(sf.relative_path);
                 const fmt = getFileFormat(sf.name);
                 return (
                   <div key={i} className={cn(
@@ -208,9 +208,9 @@ export function AudioForm({ onAdd, existingIds, showToast, scannedFiles = [] }:
                     isAlreadyAdded
                       ? theme === 'dark' ? 'bg-green-900/20 opacity-50' : 'bg-green-50 opacity-50'
                       : theme === 'dark' ? 'bg-gray-800 hover:bg-gray-700 cursor-pointer' : 'bg-white hover:bg-gray-50 cursor-pointer'
-                  )} onClick={() => !isAlreadyAdded && handleAddScannedFile(sf.name)}>
+                  )} onClick={() => !isAlreadyAdded && handleAddScannedFile(sf.relative_path)}>
                     <span className={cn('px-1.5 py-0.5 rounded text-xs text-white font-bold flex-shrink-0', formatBadge(fmt))}>{fmt}</span>
-                    <span className={cn('flex-1 truncate text-sm', theme === 'dark' ? 'text-white' : 'text-gray-800')}>{sf.name}</span>
+                    <span className={cn('flex-1 truncate text-sm', theme === 'dark' ? 'text-white' : 'text-gray-800')}>{sf.relative_path}</span>
                     <span className={cn('text-xs flex-shrink-0 hidden sm:inline', theme === 'dark' ? 'text-gray-500' : 'text-gray-400')}>{sf.size_display}</span>
                     {sf.is_vorbis && <span className="text-xs px-1 py-0.5 rounded bg-green-600 text-white flex-shrink-0">✓</span>}
                     {isAlreadyAdded ? (
@@ -80,7 +80,8 @@ export const HelpTab = memo(function HelpTab() {
               <li>• <code>notify</code> crate — {language === 'pt' ? 'File watcher em tempo real' : 'Real-time file watcher'}</li>
               <li>• <code>serde_json</code> — {language === 'pt' ? 'Serialização JSON' : 'JSON serialization'}</li>
               <li>• <code>chrono</code> — {language === 'pt' ? 'Timestamps nativos' : 'Native timestamps'}</li>
-              <li>• <code>FFmpeg</code> — {language === 'pt' ? 'Conversão de áudio WAV ↔ OGG via CLI' : 'Audio conversion WAV ↔ OGG via CLI'}</li>
+              <li>• <code>vorbis_rs</code> — {language === 'pt' ? 'Codificação OGG Vorbis embutida' : 'Built-in OGG Vorbis encoding'}</li>
+              <li>• <code>FFmpeg</code> — {language === 'pt' ? 'Conversão alternativa para formatos não suportados (opcional)' : 'Fallback conversion for unsupported formats (optional)'}</li>
               <li>• Tauri Plugin FS — {language === 'pt' ? 'Leitura de arquivos para reprodutor de áudio' : 'File reading for audio player'}</li>
               <li>• Tauri Events — {language === 'pt' ? 'Comunicação Rust → React' : 'Rust → React communication'}</li>
               <li>• Tauri Plugins — dialog, fs, clipboard, notification, opener, os, process, shell</li>
@@ -263,8 +264,8 @@ export const HelpTab = memo(function HelpTab() {
                 ? 'Play, pause, parar, volume, barra de progresso para qualquer arquivo OGG válido'
                 : 'Play, pause, stop, volume, progress bar for any valid OGG file'}</li>
               <li>• 🔄 <strong>{language === 'pt' ? 'Conversor' : 'Converter'}</strong> — {language === 'pt'
-                ? 'Converte entre WAV e OGG Vorbis usando FFmpeg nativo'
-                : 'Converts between WAV and OGG Vorbis using native FFmpeg'}</li>
+                ? 'Converte WAV, FLAC, MP3 e OGG para OGG Vorbis ou WAV sem dependências externas'
+                : 'Converts WAV, FLAC, MP3 and OGG to OGG Vorbis or WAV with no external dependencies'}</li>
               <li>• ➕ <strong>{language === 'pt' ? 'Integração' : 'Integration'}</strong> — {language === 'pt'
                 ? 'Adicione arquivos escaneados diretamente à aba Áudio com um clique'
                 : 'Add scanned files directly to the Audio tab with one click'}</li>
@@ -277,8 +278,8 @@ export const HelpTab = memo(function HelpTab() {
             </ul>
             <p className={cn('mt-3 text-sm', theme === 'dark' ? 'text-gray-400' : 'text-gray-600')}>
               ⚠️ {language === 'pt'
-                ? 'O conversor requer FFmpeg instalado no sistema (winget install ffmpeg / brew install ffmpeg).'
-                : 'The converter requires FFmpeg installed on the system (winget install ffmpeg / brew install ffmpeg).'}
+                ? 'Para outros formatos (M4A, WMA, Opus...) o conversor usa o FFmpeg, se estiver instalado (winget install ffmpeg / brew install ffmpeg).'
+                : 'For other formats (M4A, WMA, Opus...) the converter falls back to FFmpeg if it is installed (winget install ffmpeg / brew install ffmpeg).'}
             </p>
           </div>
         </div>
@@ -1,11 +1,15 @@
 import { motion, AnimatePresence } from 'framer-motion';
+import { useLanguage } from '@/contexts/LanguageContext';
 
 interface LoadingOverlayProps {
   visible: boolean;
   message?: string;
+  onCancel?: () => void;
 }
 
-export function LoadingOverlay({ visible, message = '📦 Generating...' }: LoadingOverlayProps) {
+export function LoadingOverlay({ visible, message = '📦 Generating...', onCancel }: LoadingOverlayProps) {
+  const { t } = useLanguage();
+
   return (
     <AnimatePresence>
       {visible && (
@@ -109,6 +113,15 @@ export function LoadingOverlay({ visible, message = '📦 Generating...' }: Load
                 />
               ))}
             </div>
+
+            {onCancel && (
+              <button
+                onClick={onCancel}
+                className="mt-6 px-4 py-1.5 rounded-lg bg-[#8b5a2b] hover:bg-[#a0522d] text-[#f8ecc2] text-sm font-bold transition-colors"
+              >
+                ⏹️ {t('app.cancel')}
+              </button>
+            )}
           </motion.div>
 
           {[
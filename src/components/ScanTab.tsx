@@ -1,19 +1,23 @@
-import { memo, useState, useCallback, useMemo } from 'react';
+import { memo, useState, useCallback, useMemo, useEffect } from 'react';
 import { useAppState, useAppDispatch, useTauri, useToastCtx } from '@/state/AppContext';
 import { useLanguage } from '@/contexts/LanguageContext';
 import { useTheme } from '@/contexts/ThemeContext';
 import { cn } from '@/utils/cn';
 import { AudioPlayer, PlayButton } from './AudioPlayer';
 import { AudioConverter } from './AudioConverter';
-import type { AudioFileInfo, AudioEntry } from '@/types/audio';
+import type { AudioFileInfo, AudioEntry, JukeboxConfig, RuleInfo } from '@/types/audio';
 import { originalAudios } from '@/data/originalAudios';
 
 function getFileFormat(filename: string): string {
   return (filename.split('.').pop() || 'ogg').toUpperCase();
 }
 
+function jukeboxFromTitle(file: AudioFileInfo, category: string): JukeboxConfig | null {
+  return category === 'Music' && file.title ? { name: file.title, available: true } : null;
+}
+
 export const ScanTab = memo(function ScanTab() {
-  const { assetsFolder, scanResult, watching, selectedScanFiles } = useAppState();
+  const { assetsFolder, scanResult, watching, selectedScanFiles, disabledRules } = useAppState();
   const dispatch = useAppDispatch();
   const tauri = useTauri();
   const { showToast } = useToastCtx();
@@ -22,6 +26,11 @@ export const ScanTab = memo(function ScanTab() {
 
   const [scanLoading, setScanLoading] = useState(false);
   const [playingFile, setPlayingFile] = useState<AudioFileInfo | null>(null);
+  const [rules, setRules] = useState<RuleInfo[]>([]);
+
+  useEffect(() => {
+    tauri.listScanRules().then(setRules);
+  }, [tauri.listScanRules]);
 
   const handleScanFolder = useCallback(async () => {
     setScanLoading(true);
@@ -35,12 +44,27 @@ export const ScanTab = memo(function ScanTab() {
     setScanLoading(false);
   }, [tauri]);
 
+  const handleDeepScan = useCallback(async () => {
+    setScanLoading(true);
+    try { await tauri.rescanFolder({ deep_validation: true, decode_test: true, loudness: true, silence: true, audio_fingerprint: true }); } catch (err) { console.error(err); }
+    setScanLoading(false);
+  }, [tauri]);
+
+  const duplicateOf = useMemo(() => {
+    const map = new Map<string, string>();
+    for (const group of scanResult?.duplicates ?? []) {
+      const [kept, ...copies] = group.files;
+      for (const copy of copies) if (!map.has(copy)) map.set(copy, kept.split(/[\\/]/).pop() ?? kept);
+    }
+    return map;
+  }, [scanResult]);
+
   const handlePlayFile = useCallback((file: AudioFileInfo) => {
     setPlayingFile(prev => prev?.path === file.path ? null : file);
   }, []);
 
-  const toggleFileSelection = useCallback((fileName: string) => {
-    dispatch({ type: 'TOGGLE_SCAN_FILE', payload: fileName });
+  const toggleFileSelection = useCallback((relativePath: string) => {
+    dispatch({ type: 'TOGGLE_SCAN_FILE', payload: relativePath });
   }, [dispatch]);
 
   const selectAllValid = useCallback(() => {
@@ -63,7 +87,7 @@ export const ScanTab = memo(function ScanTab() {
     const id = file.name.replace(/\.[^.]+$/, '');
     const original = originalAudios.find(a => a.id.toLowerCase() === id.toLowerCase());
     const category = detectCategory(file.name);
-    const audio: AudioEntry = { id: original ? original.id : id, type: original ? 'replace' : 'custom', originalName: original?.name || null, category, files: [file.name], looped: category === 'Music', jukebox: null };
+    const audio: AudioEntry = { id: original ? original.id : id, type: original ? 'replace' : 'custom', originalName: original?.name || null, category, files: [file.relative_path], looped: category === 'Music', jukebox: jukeboxFromTitle(file, category) };
     dispatch({ type: 'ADD_AUDIO', payload: audio });
     showToast(`✅ ${file.name} ${t('scan.audioAdded')}`, 'success');
   }, [dispatch, showToast, t, detectCategory]);
@@ -71,13 +95,13 @@ export const ScanTab = memo(function ScanTab() {
   const handleAddSelected = useCallback(() => {
     if (selectedScanFiles.length === 0 || !scanResult?.files) return;
     let count = 0;
-    for (const fileName of selectedScanFiles) {
-      const file = scanResult.files.find(f => f.name === fileName);
+    for (const relativePath of selectedScanFiles) {
+      const file = scanResult.files.find(f => f.relative_path === relativePath);
       if (!file) continue;
       const id = file.name.replace(/\.[^.]+$/, '');
       const original = originalAudios.find(a => a.id.toLowerCase() === id.toLowerCase());
       const category = detectCategory(file.name);
-      dispatch({ type: 'ADD_AUDIO', payload: { id: original ? original.id : id, type: original ? 'replace' : 'custom', originalName: original?.name || null, category, files: [file.name], looped: category === 'Music', jukebox: null } });
+      dispatch({ type: 'ADD_AUDIO', payload: { id: original ? original.id : id, type: original ? 'replace' : 'custom', originalName: original?.name || null, category, files: [file.relative_path], looped: category === 'Music', jukebox: jukeboxFromTitle(file, category) } });
       count++;
     }
     showToast(`✅ ${count} ${t('scan.audioAdded')}`, 'success');
@@ -86,19 +110,10 @@ export const ScanTab = memo(function ScanTab() {
 
   const stats = useMemo(() => {
     if (!scanResult) return null;
-    let validCount = 0;
-    
-    scanResult.files.forEach(f => {
-      const fmt = getFileFormat(f.name);
-      if ((fmt === 'OGG' && f.is_vorbis) || fmt === 'WAV') {
-        validCount++;
-      }
-    });
-
     return { 
       total: scanResult.files.length, 
-      valid: validCount,
-      invalid: scanResult.files.length - validCount, 
+      valid: scanResult.total_valid,
+      invalid: scanResult.files.length - scanResult.total_valid, 
       size: scanResult.total_size 
     };
   }, [scanResult]);
@@ -124,6 +139,7 @@ export const ScanTab = memo(function ScanTab() {
         {assetsFolder && (
           <>
             <button onClick={handleRescan} disabled={scanLoading} className={btnClass(theme === 'dark' ? 'bg-blue-600 border-b-3 border-blue-700' : 'bg-gradient-to-b from-[#5aa0e9] to-[#3a80c0] border-b-3 border-[#2a5a8a]')}>🔄 {t('scan.refresh')}</button>
+            <button onClick={handleDeepScan} disabled={scanLoading} className={btnClass(theme === 'dark' ? 'bg-blue-600 border-b-3 border-blue-700' : 'bg-gradient-to-b from-[#5aa0e9] to-[#3a80c0] border-b-3 border-[#2a5a8a]')}>🔬 {t('scan.deepScan')}</button>
             <button onClick={() => tauri.openInExplorer(assetsFolder)} className={btnClass(theme === 'dark' ? 'bg-orange-600 border-b-3 border-orange-700' : 'bg-gradient-to-b from-[#e07020] to-[#d06010] border-b-3 border-[#a04808]')}>📁 Explorer</button>
             <button onClick={tauri.watchFolder} disabled={watching} className={cn(btnClass(theme === 'dark' ? 'bg-blue-600 border-b-3 border-blue-700' : 'bg-gradient-to-b from-[#5aa0e9] to-[#3a80c0] border-b-3 border-[#2a5a8a]'), watching && 'opacity-50')}>
               {watching ? `✅ ${t('scan.watching')}` : `👁️ ${t('scan.watch')}`}
@@ -138,6 +154,21 @@ export const ScanTab = memo(function ScanTab() {
         </div>
       )}
 
+      {rules.length > 0 && (
+        <details className={cn('p-3 rounded-lg border-2', theme === 'dark' ? 'bg-gray-700/50 border-gray-600 text-gray-200' : 'bg-white/60 border-[#8b4513]/30 text-[#5c3d2e]')}>
+          <summary className="font-bold cursor-pointer">📏 {t('scan.rules')} ({rules.length - disabledRules.length}/{rules.length})</summary>
+          <p className="text-sm opacity-70 mt-1">{t('scan.rulesHint')}</p>
+          <div className="grid sm:grid-cols-2 gap-1 mt-2">
+            {rules.map(rule => (
+              <label key={rule.id} className="flex items-center gap-2 text-sm" title={rule.id}>
+                <input type="checkbox" checked={!disabledRules.includes(rule.id)} onChange={() => dispatch({ type: 'TOGGLE_RULE', payload: rule.id })} className="w-4 h-4 accent-blue-500 flex-shrink-0" />
+                {rule.severity === 'error' ? '❌' : '⚠️'} {rule.description}
+              </label>
+            ))}
+          </div>
+        </details>
+      )}
+
       {playingFile && <AudioPlayer file={playingFile} onClose={() => setPlayingFile(null)} />}
       
       {scanResult && scanResult.files.length > 0 && <AudioConverter />}
@@ -162,11 +193,23 @@ export const ScanTab = memo(function ScanTab() {
         </div>
       )}
 
+      {scanResult?.summary && scanResult.files.length > 0 && (
+        <div className={cn('text-sm flex flex-wrap gap-x-4 gap-y-1', theme === 'dark' ? 'text-gray-400' : 'text-[#8b6914]')}>
+          <span>🎼 {Object.entries(scanResult.summary.by_codec).map(([k, v]) => `${k} ${v}`).join(' · ')}</span>
+          <span>📈 {Object.entries(scanResult.summary.by_sample_rate).map(([k, v]) => `${k} ${v}`).join(' · ')}</span>
+          <span>🔈 {Object.entries(scanResult.summary.by_channels).map(([k, v]) => `${k} ${v}`).join(' · ')}</span>
+          <span>⏱️ {(scanResult.summary.total_duration_secs / 60).toFixed(1)} min{scanResult.summary.average_duration_secs != null && ` (~${scanResult.summary.average_duration_secs.toFixed(1)}s)`}</span>
+        </div>
+      )}
+
       {scanResult && scanResult.files.length > 0 && (
         <div className="flex flex-wrap gap-2">
           <button onClick={selectedScanFiles.length > 0 ? deselectAll : selectAllValid} className={cn('px-3 py-1.5 rounded-lg text-sm font-bold text-white transition-all', theme === 'dark' ? 'bg-gray-600 hover:bg-gray-500' : 'bg-gray-500 hover:bg-gray-400')}>
             {selectedScanFiles.length > 0 ? `☐ ${t('scan.deselect')} (${selectedScanFiles.length})` : `☑ ${t('scan.selectAll')}`}
           </button>
+          <button onClick={tauri.exportScanSummary} className={cn('px-3 py-1.5 rounded-lg text-sm font-bold text-white transition-all', theme === 'dark' ? 'bg-blue-600 hover:bg-blue-500' : 'bg-blue-500 hover:bg-blue-400')}>
+            📊 {t('scan.exportSummary')}
+          </button>
           {selectedScanFiles.length > 0 && (
             <button onClick={handleAddSelected} className={cn('px-3 py-1.5 rounded-lg text-sm font-bold text-white transition-all', theme === 'dark' ? 'bg-green-600 hover:bg-green-500' : 'bg-green-500 hover:bg-green-400')}>
               ➕ {selectedScanFiles.length} {t('scan.addSelected')}
@@ -182,14 +225,14 @@ export const ScanTab = memo(function ScanTab() {
             {scanResult.files.map((file, i) => {
               const fmt = getFileFormat(file.name);
               
+              const isValid = file.is_valid_ogg;
               const isOggVorbis = fmt === 'OGG' && file.is_vorbis;
-              const isWav = fmt === 'WAV';
-              const isValid = isOggVorbis || isWav;
+              const isWav = fmt === 'WAV' && isValid;
               
               const isOggNotVorbis = fmt === 'OGG' && !file.is_vorbis;
               const isInvalid = !isValid;
 
-              const isSelected = selectedScanFiles.includes(file.name);
+              const isSelected = selectedScanFiles.includes(file.relative_path);
               const isCurrentlyPlaying = playingFile?.path === file.path;
 
               return (
@@ -204,28 +247,36 @@ export const ScanTab = memo(function ScanTab() {
                   <input 
                     type="checkbox" 
                     checked={isSelected} 
-                    onChange={() => toggleFileSelection(file.name)} 
+                    onChange={() => toggleFileSelection(file.relative_path)} 
                     className="w-4 h-4 accent-blue-500 flex-shrink-0" 
                   />
                   <PlayButton onClick={() => handlePlayFile(file)} isPlaying={isCurrentlyPlaying} />
                   <div className="flex-1 min-w-0">
                     <div className={cn('font-bold truncate flex items-center gap-2', theme === 'dark' ? 'text-white' : 'text-[#5c3d2e]')}>
                       {isValid ? '✅' : isOggNotVorbis ? '❌' : '⚠️'} 
-                      {file.name}
+                      {file.relative_path}
                     </div>
+                    {(file.title || file.artist) && <div className={cn('text-sm truncate', theme === 'dark' ? 'text-gray-400' : 'text-[#8b6914]')}>🎤 {[file.title, file.artist, file.album].filter(Boolean).join(' · ')}</div>}
                     {file.error && <div className={cn('text-sm mt-0.5', theme === 'dark' ? 'text-red-400' : 'text-red-600')}>{file.error}</div>}
+                    {file.violations?.filter(v => v.message !== file.error).map(v => <div key={v.rule} className={cn('text-sm mt-0.5', v.severity === 'error' ? theme === 'dark' ? 'text-red-400' : 'text-red-600' : theme === 'dark' ? 'text-yellow-400' : 'text-yellow-700')}>{v.severity === 'error' ? '❌' : '⚠️'} {v.message}</div>)}
                   </div> 
                   <div className="flex flex-wrap gap-1.5 flex-shrink-0">
                     <span className={cn('px-2 py-0.5 rounded text-xs text-white font-bold', formatBadge(fmt))}>{fmt}</span>
+                    {file.detected_format && file.detected_format !== fmt && <span className="px-2 py-0.5 rounded text-xs text-white bg-red-600" title={file.error ?? undefined}>⚠️ {file.detected_format}</span>}
                     
                     {isOggVorbis && <span className="px-2 py-0.5 rounded text-xs text-white bg-green-600">Vorbis ✓</span>}
                     {isWav && <span className="px-2 py-0.5 rounded text-xs text-white bg-purple-600">WAV ✓</span>}
-                    {isOggNotVorbis && <span className="px-2 py-0.5 rounded text-xs text-white bg-red-600">NOT Vorbis</span>}
+                    {isOggNotVorbis && <span className="px-2 py-0.5 rounded text-xs text-white bg-red-600">{file.codec ? `${file.codec} ✗` : 'NOT Vorbis'}</span>}
                     
                     {isInvalid && <span className="px-2 py-0.5 rounded text-xs text-white bg-yellow-600">{t('scan.convert')}</span>}
                     
                     <span className={cn('px-2 py-0.5 rounded text-xs', theme === 'dark' ? 'bg-gray-600 text-gray-200' : 'bg-gray-200')}>{file.size_display}</span>
+                    {file.codec && fmt === 'WAV' && <span className={cn('px-2 py-0.5 rounded text-xs', theme === 'dark' ? 'bg-gray-600 text-gray-200' : 'bg-gray-200')}>{file.codec}{file.bits_per_sample ? ` ${file.bits_per_sample}-bit` : ''}</span>}
                     {file.sample_rate && <span className={cn('px-2 py-0.5 rounded text-xs', theme === 'dark' ? 'bg-gray-600 text-gray-200' : 'bg-gray-200')}>{file.sample_rate}Hz</span>}
+                    {file.loudness?.integrated_lufs != null && <span className={cn('px-2 py-0.5 rounded text-xs', file.loudness.clipped_samples > 0 ? 'bg-red-600 text-white' : theme === 'dark' ? 'bg-gray-600 text-gray-200' : 'bg-gray-200')} title={`LRA ${file.loudness.loudness_range_lu?.toFixed(1) ?? '-'} LU · TP ${file.loudness.true_peak_dbtp?.toFixed(1) ?? '-'} dBTP · clip ${file.loudness.clipped_samples}`}>🔊 {file.loudness.integrated_lufs.toFixed(1)} LUFS</span>}
+                    {duplicateOf.has(file.path) && <span className="px-2 py-0.5 rounded text-xs text-white bg-orange-600" title={duplicateOf.get(file.path)}>🧬 {t('scan.duplicate')}</span>}
+                    {file.silence && (file.silence.leading_secs > 0.05 || file.silence.trailing_secs > 0.05) && <span className={cn('px-2 py-0.5 rounded text-xs', theme === 'dark' ? 'bg-yellow-700 text-yellow-100' : 'bg-yellow-200 text-yellow-800')} title={`< ${file.silence.threshold_db} dBFS`}>🔇 {file.silence.leading_secs.toFixed(2)}s / {file.silence.trailing_secs.toFixed(2)}s</span>}
+                    {file.loop_points && <span className={cn('px-2 py-0.5 rounded text-xs', theme === 'dark' ? 'bg-gray-600 text-gray-200' : 'bg-gray-200')} title={`LOOPSTART ${file.loop_points.start_sample}`}>🔁 {file.loop_points.start_secs.toFixed(1)}s</span>}
                     {file.channels && <span className={cn('px-2 py-0.5 rounded text-xs', theme === 'dark' ? 'bg-gray-600 text-gray-200' : 'bg-gray-200')}>{file.channels === 1 ? 'Mono' : 'Stereo'}</span>}
                   </div>
                   <button onClick={() => handleAddToAudio(file)} className={cn('px-2 py-1 rounded text-xs font-bold text-white transition-all hover:scale-105 flex-shrink-0', theme === 'dark' ? 'bg-green-600 hover:bg-green-500' : 'bg-green-500 hover:bg-green-400')} title={t('scan.addToAudio')}>
@@ -31,6 +31,7 @@ const translations: Record<Language, Translations> = {
     'app.nativeDesktop': 'Native Desktop',
     'app.webShortcuts': 'Versão Web • Cross-save compatível',
     'app.desktopShortcuts': 'Ctrl+1-5: Abas | Ctrl+S: Salvar | Ctrl+O: Abrir',
+    'app.cancel': 'Cancelar',
 
     // Setup
     'setup.title': 'Configuração do Mod',
@@ -116,6 +117,11 @@ const translations: Record<Language, Translations> = {
     'scan.description': 'Escaneia pastas por arquivos de áudio (OGG, MP3, WAV, FLAC, etc). Reproduza, converta e adicione à lista do mod.',
     'scan.selectFolder': 'Selecionar Pasta',
     'scan.refresh': 'Atualizar',
+    'scan.deepScan': 'Validação completa',
+    'scan.duplicate': 'Duplicado',
+    'scan.exportSummary': 'Exportar resumo',
+    'scan.rules': 'Regras de compatibilidade',
+    'scan.rulesHint': 'Regras desmarcadas são ignoradas no próximo escaneamento',
     'scan.opening': 'Abrindo...',
     'scan.total': 'Total',
     'scan.valid': 'Válidos',
@@ -190,6 +196,7 @@ const translations: Record<Language, Translations> = {
     'app.nativeDesktop': 'Native Desktop',
     'app.webShortcuts': 'Web Version • Cross-save compatible',
     'app.desktopShortcuts': 'Ctrl+1-5: Tabs | Ctrl+S: Save | Ctrl+O: Open',
+    'app.cancel': 'Cancel',
 
     // Setup
     'setup.title': 'Mod Configuration',
@@ -275,6 +282,11 @@ const translations: Record<Language, Translations> = {
     'scan.description': 'Scan folders for audio files (OGG, MP3, WAV, FLAC, etc). Play, convert and add to mod list.',
     'scan.selectFolder': 'Select Folder',
     'scan.refresh': 'Refresh',
+    'scan.deepScan': 'Deep validation',
+    'scan.duplicate': 'Duplicate',
+    'scan.exportSummary': 'Export summary',
+    'scan.rules': 'Compatibility rules',
+    'scan.rulesHint': 'Unchecked rules are skipped on the next scan',
     'scan.opening': 'Opening...',
     'scan.total': 'Total',
     'scan.valid': 'Valid',
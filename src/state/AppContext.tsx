@@ -15,9 +15,21 @@ import type {
   AppState,
   AppAction,
   AudioEntry,
+  AudioEntryWarning,
+  BatchConvertResult,
+  ConvertJob,
+  ConvertProgress,
+  EncodingOptions,
+  EncodingPreset,
   ModConfig,
+  OutputOptions,
+  RuleInfo,
+  ScanOptions,
+  ScanProgress,
+  ScanResult,
   ToastType,
   TabType,
+  ToolchainStatus,
 } from "@/types/audio";
 import { originalAudios } from "@/data/originalAudios";
 import { generateAndDownloadZipWeb } from "@/utils/zipWeb";
@@ -156,6 +168,28 @@ async function clipboardWrite(text: string): Promise<void> {
   }
 }
 
+async function listenEvent<T>(
+  event: string,
+  handler: (payload: T) => void
+): Promise<() => void> {
+  const w = window as any;
+  const transformCallback = w.__TAURI_INTERNALS__?.transformCallback;
+  if (!detectTauri() || !transformCallback) return () => {};
+  try {
+    const eventId = await invoke<number>("plugin:event|listen", {
+      event,
+      target: { kind: "Any" },
+      handler: transformCallback((e: { payload: T }) => handler(e.payload)),
+    });
+    return () => {
+      invoke("plugin:event|unlisten", { event, eventId }).catch(() => {});
+    };
+  } catch (err) {
+    console.error("Event listen error:", err);
+    return () => {};
+  }
+}
+
 async function openerReveal(path: string): Promise<void> {
   if (!detectTauri()) {
     console.warn("Opener only available in Desktop mode");
@@ -306,6 +340,8 @@ function saveToLocalStorage(state: AppState) {
     const data = {
       config: state.modConfig,
       audios: state.audios,
+      disabled_rules: state.disabledRules,
+      encoding_options: state.encodingOptions,
       version: SAVE_FORMAT_VERSION,
       platform: "web",
       saved_at: new Date().toISOString(),
@@ -319,6 +355,8 @@ function saveToLocalStorage(state: AppState) {
 function loadFromLocalStorage(): {
   config: ModConfig;
   audios: AudioEntry[];
+  disabledRules: string[];
+  encodingOptions?: EncodingOptions;
 } | null {
   try {
     const raw = localStorage.getItem(STORAGE_KEY);
@@ -328,6 +366,8 @@ function loadFromLocalStorage(): {
     return {
       config: configFromSave(data.config),
       audios: audiosFromSave(data.audios),
+      disabledRules: data.disabled_rules ?? [],
+      encodingOptions: data.encoding_options,
     };
   } catch (err) {
     console.error("Failed to load auto-save from LS:", err);
@@ -340,7 +380,14 @@ type ToastState = { visible: boolean; message: string; type: ToastType };
 
 interface TauriAPI {
   scanFolder: () => Promise<void>;
-  rescanFolder: () => Promise<void>;
+  rescanFolder: (options?: ScanOptions) => Promise<void>;
+  cancelScan: () => Promise<void>;
+  exportScanSummary: () => Promise<void>;
+  listScanRules: () => Promise<RuleInfo[]>;
+  listEncodingPresets: () => Promise<EncodingPreset[]>;
+  getToolchainStatus: (refresh?: boolean) => Promise<ToolchainStatus | null>;
+  chooseFfmpegPath: (clear?: boolean) => Promise<ToolchainStatus | null>;
+  isScanning: boolean;
   watchFolder: () => Promise<void>;
   openInExplorer: (path: string) => Promise<void>;
   copyToClipboard: (text: string) => Promise<void>;
@@ -361,6 +408,12 @@ interface TauriAPI {
     sourcePath: string,
     targetFormat: "ogg" | "wav"
   ) => Promise<any>;
+  convertBatch: (
+    files: { path: string; name: string }[],
+    targetFormat: "ogg" | "wav",
+    output?: OutputOptions
+  ) => Promise<BatchConvertResult | null>;
+  cancelConversion: (jobId?: string) => Promise<void>;
   isDesktop: boolean;
 }
 
@@ -392,6 +445,15 @@ export function AppProvider({ children }: { children: ReactNode }) {
   const isDesktop = useMemo(() => detectTauri(), []);
 
 
+  // Skips saving the defaults the app starts with before the saved settings are read.
+  const encodingSettingsReady = useRef(false);
+  useEffect(() => {
+    if (!isDesktop || !encodingSettingsReady.current) return;
+    invoke("save_encoding_settings", { options: state.encodingOptions }).catch((err) =>
+      console.error("❌ Encoding settings save failed:", err)
+    );
+  }, [isDesktop, state.encodingOptions]);
+
   useEffect(() => {
     const interval = setInterval(() => {
       const currentState = stateRef.current;
@@ -404,6 +466,8 @@ export function AppProvider({ children }: { children: ReactNode }) {
         const projectData = {
           config: configToRust(currentState.modConfig),
           audios: audiosToRust(currentState.audios),
+          disabled_rules: currentState.disabledRules,
+          encoding_options: currentState.encodingOptions,
           version: SAVE_FORMAT_VERSION,
           platform: "desktop",
           saved_at: new Date().toISOString(),
@@ -432,6 +496,8 @@ export function AppProvider({ children }: { children: ReactNode }) {
             const payload = {
               config: configFromSave(savedData.config),
               audios: audiosFromSave(savedData.audios),
+              disabledRules: savedData.disabled_rules ?? [],
+              encodingOptions: savedData.encoding_options,
             };
             dispatch({ type: "LOAD_PROJECT", payload });
             setToast({ visible: true, message: "🔄 Sessão restaurada!", type: "info" });
@@ -440,6 +506,14 @@ export function AppProvider({ children }: { children: ReactNode }) {
         } catch (err) {
           console.warn("⚠️ Sem auto-save desktop ou erro ao ler:", err);
         }
+        // The converter options are an app setting: the last ones chosen win over the session's.
+        try {
+          const encoding = await invoke<EncodingOptions | null>("load_encoding_settings");
+          if (encoding) dispatch({ type: "RESTORE_ENCODING_OPTIONS", payload: encoding });
+        } catch (err) {
+          console.warn("⚠️ Erro ao ler as opções de codificação:", err);
+        }
+        encodingSettingsReady.current = true;
       } else {
         const saved = loadFromLocalStorage();
         if (saved) {
@@ -472,6 +546,8 @@ export function AppProvider({ children }: { children: ReactNode }) {
           const payload = {
             config: configFromSave(data.config),
             audios: audiosFromSave(data.audios),
+            disabledRules: data.disabled_rules ?? [],
+            encodingOptions: data.encoding_options,
           };
           dispatch({ type: "LOAD_PROJECT", payload });
           showToast("📂 Projeto carregado!", "success");
@@ -502,6 +578,43 @@ export function AppProvider({ children }: { children: ReactNode }) {
   }, [isDesktop, handleFileSelect]);
 
 
+  const [isScanning, setIsScanning] = useState(false);
+
+  const runScan = useCallback(
+    async (folder: string, message: string, options?: ScanOptions) => {
+      dispatch({ type: "SET_LOADING", payload: { loading: true, message } });
+      setIsScanning(true);
+      const unlisten = await listenEvent<ScanProgress>(
+        "scan-progress",
+        (p) =>
+          dispatch({
+            type: "SET_LOADING",
+            payload: {
+              loading: true,
+              message: `${message} ${p.done}/${p.total} · ${p.current_file}`,
+            },
+          })
+      );
+      try {
+        const result = await invoke<ScanResult>("scan_audio_folder", {
+          folderPath: folder,
+          options: {
+            find_duplicates: true,
+            disabled_rules: stateRef.current.disabledRules,
+            ...options,
+          },
+        });
+        dispatch({ type: "SET_SCAN_RESULT", payload: result });
+        return result;
+      } finally {
+        unlisten();
+        setIsScanning(false);
+        dispatch({ type: "SET_LOADING", payload: { loading: false } });
+      }
+    },
+    []
+  );
+
   const scanFolder = useCallback(async () => {
     if (!isDesktop) return;
     try {
@@ -511,49 +624,115 @@ export function AppProvider({ children }: { children: ReactNode }) {
       });
       if (!folder || Array.isArray(folder)) return;
 
-      dispatch({
-        type: "SET_LOADING",
-        payload: { loading: true, message: "🔍 Escaneando..." },
-      });
-      const result = await invoke("scan_audio_folder", { folderPath: folder });
-      dispatch({
-        type: "SET_SCAN_RESULT",
-        payload: result as AppState["scanResult"],
-      });
+      const result = await runScan(folder, "🔍 Escaneando...");
       dispatch({ type: "SET_ASSETS_FOLDER", payload: folder });
-      dispatch({ type: "SET_LOADING", payload: { loading: false } });
       showToast(
-        `🔍 Encontrados ${(result as any)?.files?.length ?? 0} arquivos`,
-        "success"
+        result.cancelled
+          ? `⏹️ Escaneamento cancelado: ${result.files.length} arquivos`
+          : `🔍 Encontrados ${result.files.length} arquivos`,
+        result.cancelled ? "info" : "success"
       );
     } catch (err) {
-      dispatch({ type: "SET_LOADING", payload: { loading: false } });
       showToast(`Erro: ${err}`, "error");
     }
-  }, [showToast, isDesktop]);
+  }, [showToast, isDesktop, runScan]);
 
-  const rescanFolder = useCallback(async () => {
+  const rescanFolder = useCallback(async (options?: ScanOptions) => {
     if (!isDesktop) return;
     const folder = stateRef.current.assetsFolder;
     if (!folder) return;
     try {
-      dispatch({
-        type: "SET_LOADING",
-        payload: { loading: true, message: "🔄 Atualizando..." },
-      });
-      const result = await invoke("scan_audio_folder", { folderPath: folder });
-      dispatch({
-        type: "SET_SCAN_RESULT",
-        payload: result as AppState["scanResult"],
+      const result = await runScan(folder, "🔄 Atualizando...", options);
+      showToast(
+        result.cancelled ? "⏹️ Escaneamento cancelado" : "🔄 Atualizado!",
+        result.cancelled ? "info" : "success"
+      );
+    } catch (err) {
+      showToast(`Erro: ${err}`, "error");
+    }
+  }, [showToast, isDesktop, runScan]);
+
+  const exportScanSummary = useCallback(async () => {
+    if (!isDesktop) return;
+    const summary = stateRef.current.scanResult?.summary;
+    if (!summary) return;
+    try {
+      const path = await dialogSave({
+        defaultPath: "scan-summary.csv",
+        filters: [
+          { name: "CSV", extensions: ["csv"] },
+          { name: "JSON", extensions: ["json"] },
+        ],
+        title: "Salvar resumo",
       });
-      dispatch({ type: "SET_LOADING", payload: { loading: false } });
-      showToast("🔄 Atualizado!", "success");
+      if (!path) return;
+      await invoke("export_scan_summary", { filePath: path, summary });
+      showToast("📊 Resumo salvo!", "success");
+    } catch (err) {
+      showToast(`Erro: ${err}`, "error");
+    }
+  }, [showToast, isDesktop]);
+
+  const cancelScan = useCallback(async () => {
+    if (!isDesktop) return;
+    try {
+      await invoke("cancel_scan");
     } catch (err) {
-      dispatch({ type: "SET_LOADING", payload: { loading: false } });
       showToast(`Erro: ${err}`, "error");
     }
   }, [showToast, isDesktop]);
 
+  const listScanRules = useCallback(async () => {
+    if (!isDesktop) return [];
+    try {
+      return await invoke<RuleInfo[]>("list_scan_rules");
+    } catch (err) {
+      console.error("Rule list error:", err);
+      return [];
+    }
+  }, [isDesktop]);
+
+  const listEncodingPresets = useCallback(async () => {
+    if (!isDesktop) return [];
+    try {
+      return await invoke<EncodingPreset[]>("list_encoding_presets");
+    } catch (err) {
+      console.error("Preset list error:", err);
+      return [];
+    }
+  }, [isDesktop]);
+
+  const getToolchainStatus = useCallback(async (refresh = false) => {
+    if (!isDesktop) return null;
+    try {
+      return await invoke<ToolchainStatus>("get_toolchain_status", { refresh });
+    } catch (err) {
+      console.error("Toolchain status error:", err);
+      return null;
+    }
+  }, [isDesktop]);
+
+  const chooseFfmpegPath = useCallback(async (clear = false) => {
+    if (!isDesktop) return null;
+    let path: string | null = null;
+    if (!clear) {
+      const selected = await dialogOpen({ title: "FFmpeg" });
+      if (!selected || Array.isArray(selected)) return null;
+      path = selected;
+    }
+    try {
+      const status = await invoke<ToolchainStatus>("set_ffmpeg_path", { path });
+      showToast(
+        status.ffmpeg ? `🧰 FFmpeg ${status.ffmpeg.version}` : "FFmpeg não encontrado",
+        status.ffmpeg ? "success" : "error"
+      );
+      return status;
+    } catch (err) {
+      showToast(`Erro: ${err}`, "error");
+      return null;
+    }
+  }, [isDesktop, showToast]);
+
   const watchFolder = useCallback(async () => {
     if (!isDesktop) return;
     const folder = stateRef.current.assetsFolder;
@@ -623,6 +802,8 @@ export function AppProvider({ children }: { children: ReactNode }) {
     const projectData = {
       config: configToRust(s.modConfig),
       audios: audiosToRust(s.audios),
+      disabled_rules: s.disabledRules,
+      encoding_options: s.encodingOptions,
       version: SAVE_FORMAT_VERSION,
       platform: isDesktop ? "desktop" : "web",
       saved_at: new Date().toISOString(),
@@ -670,6 +851,8 @@ export function AppProvider({ children }: { children: ReactNode }) {
         const payload = {
           config: configFromSave(data.config),
           audios: audiosFromSave(data.audios),
+          disabledRules: data.disabled_rules ?? [],
+          encodingOptions: data.encoding_options,
         };
         dispatch({ type: "LOAD_PROJECT", payload });
         const source =
@@ -687,6 +870,26 @@ export function AppProvider({ children }: { children: ReactNode }) {
     }
   }, [showToast, isDesktop]);
 
+  const confirmAudioWarnings = useCallback(async () => {
+    const s = stateRef.current;
+    if (!s.assetsFolder) return true;
+    try {
+      const warnings = await invoke<AudioEntryWarning[]>("check_audio_entries", {
+        audioSourceFolder: s.assetsFolder,
+        audios: audiosToRust(s.audios),
+      });
+      if (warnings.length === 0) return true;
+      const lines = warnings.map((w) => `• ${w.audio_id} (${w.file}): ${w.message}`);
+      return await dialogConfirm(
+        `${lines.join("\n")}\n\nExportar mesmo assim?`,
+        `⚠️ ${warnings.length} avisos`
+      );
+    } catch (err) {
+      console.error("Audio check error:", err);
+      return true;
+    }
+  }, []);
+
   const exportToFolder = useCallback(
     async (copyAudio: boolean) => {
       if (!isDesktop) return;
@@ -695,6 +898,7 @@ export function AppProvider({ children }: { children: ReactNode }) {
         showToast("Adicione áudios primeiro!", "error");
         return;
       }
+      if (!(await confirmAudioWarnings())) return;
       try {
         const folder = await dialogOpen({
           directory: true,
@@ -723,7 +927,7 @@ export function AppProvider({ children }: { children: ReactNode }) {
         showToast(`Erro: ${err}`, "error");
       }
     },
-    [showToast, isDesktop]
+    [showToast, isDesktop, confirmAudioWarnings]
   );
 
   const exportToZip = useCallback(
@@ -734,6 +938,7 @@ export function AppProvider({ children }: { children: ReactNode }) {
         showToast("Adicione áudios primeiro!", "error");
         return;
       }
+      if (!(await confirmAudioWarnings())) return;
       try {
         const clean = s.modConfig.modName
           .replace(/[^a-zA-Z0-9 ]/g, "")
@@ -762,7 +967,7 @@ export function AppProvider({ children }: { children: ReactNode }) {
         showToast(`Erro: ${err}`, "error");
       }
     },
-    [showToast, isDesktop]
+    [showToast, isDesktop, confirmAudioWarnings]
   );
 
   const getManifestJson = useCallback(async () => {
@@ -887,10 +1092,89 @@ export function AppProvider({ children }: { children: ReactNode }) {
     [isDesktop]
   );
 
+  const convertBatch = useCallback(
+    async (files: { path: string; name: string }[], targetFormat: "ogg" | "wav", output?: OutputOptions) => {
+      if (!isDesktop || files.length === 0) return null;
+      const batchId = Date.now();
+      const jobs = files.map((f, i) => ({ id: `${batchId}-${i}-${f.name}`, source_path: f.path }));
+      files.forEach((f, i) =>
+        dispatch({
+          type: "ADD_CONVERT_JOB",
+          payload: { id: jobs[i].id, sourceFile: f.name, targetFormat, status: "pending", progress: 0 },
+        })
+      );
+
+      const statusMap: Record<ConvertProgress["status"], ConvertJob["status"]> = {
+        queued: "pending",
+        converting: "converting",
+        done: "done",
+        skipped: "skipped",
+        failed: "error",
+        cancelled: "cancelled",
+      };
+      const unlisten = await listenEvent<ConvertProgress>("convert-progress", (p) =>
+        dispatch({
+          type: "UPDATE_CONVERT_JOB",
+          payload: {
+            id: p.job_id,
+            updates: {
+              status: statusMap[p.status],
+              progress: Math.round(p.progress * 100),
+              error: p.error ?? undefined,
+            },
+          },
+        })
+      );
+      try {
+        const result = await invoke<BatchConvertResult>("convert_batch", {
+          jobs,
+          targetFormat,
+          options: stateRef.current.encodingOptions,
+          output: output ?? null,
+        });
+        for (const job of result.jobs) {
+          if (job.result) {
+            dispatch({ type: "UPDATE_CONVERT_JOB", payload: { id: job.id, updates: { outputPath: job.result.output_path, backupPath: job.result.backup_path, outputInfo: job.result.output_info, loudness: job.result.loudness, edit: job.result.edit } } });
+          }
+        }
+        showToast(
+          `🔄 ${result.succeeded} ✅ · ${result.failed} ❌${result.skipped > 0 ? ` · ${result.skipped} ⏭️` : ""}${result.cancelled > 0 ? ` · ${result.cancelled} ⏹️` : ""}`,
+          result.failed > 0 ? "error" : "success"
+        );
+        return result;
+      } catch (err) {
+        showToast(`Erro: ${err}`, "error");
+        return null;
+      } finally {
+        unlisten();
+      }
+    },
+    [showToast, isDesktop]
+  );
+
+  const cancelConversion = useCallback(
+    async (jobId?: string) => {
+      if (!isDesktop) return;
+      try {
+        await invoke("cancel_conversion", { jobId: jobId ?? null });
+      } catch (err) {
+        showToast(`Erro: ${err}`, "error");
+      }
+    },
+    [showToast, isDesktop]
+  );
+
   const tauri = useMemo<TauriAPI>(
     () => ({
       scanFolder,
       rescanFolder,
+      cancelScan,
+      exportScanSummary,
+      listScanRules,
+      listEncodingPresets,
+      getToolchainStatus,
+      chooseFfmpegPath,
+      isScanning,
       watchFolder,
       openInExplorer,
       copyToClipboard,
@@ -908,11 +1192,20 @@ export function AppProvider({ children }: { children: ReactNode }) {
       downloadI18n,
       downloadZip,
       convertAudio,
+      convertBatch,
+      cancelConversion,
       isDesktop,
     }),
     [
       scanFolder,
       rescanFolder,
+      cancelScan,
+      exportScanSummary,
+      listScanRules,
+      listEncodingPresets,
+      getToolchainStatus,
+      chooseFfmpegPath,
+      isScanning,
       watchFolder,
       openInExplorer,
       copyToClipboard,
@@ -930,6 +1223,8 @@ export function AppProvider({ children }: { children: ReactNode }) {
       downloadI18n,
       downloadZip,
       convertAudio,
+      convertBatch,
+      cancelConversion,
       isDesktop,
     ]
   );
@@ -1,5 +1,5 @@
 import { produce } from 'immer';
-import type { AppState, AppAction, ModConfig, PlayerState } from '@/types/audio';
+import type { AppState, AppAction, EncodingOptions, ModConfig, PlayerState } from '@/types/audio';
 
 export const defaultConfig: ModConfig = {
   modId: 'SeuNome.AudioMod',
@@ -18,6 +18,15 @@ export const defaultPlayer: PlayerState = {
   duration: 0,
 };
 
+export const defaultEncodingOptions: EncodingOptions = {
+  quality: 0.6,
+  sample_rate: null,
+  channels: 'keep',
+  max_bitrate_kbps: null,
+  normalize: null,
+  edit: { trim: { mode: 'none' }, fade_in_ms: 0, fade_out_ms: 0, loop_seam_ms: 0 },
+};
+
 export const initialState: AppState = {
   modConfig: defaultConfig,
   audios: [],
@@ -31,6 +40,8 @@ export const initialState: AppState = {
   player: defaultPlayer,
   convertJobs: [],
   selectedScanFiles: [],
+  disabledRules: [],
+  encodingOptions: defaultEncodingOptions,
 };
 
 export function appReducer(state: AppState, action: AppAction): AppState {
@@ -99,6 +110,8 @@ export function appReducer(state: AppState, action: AppAction): AppState {
       case 'LOAD_PROJECT':
         draft.modConfig = action.payload.config;
         draft.audios = action.payload.audios;
+        draft.disabledRules = action.payload.disabledRules ?? [];
+        draft.encodingOptions = { ...defaultEncodingOptions, ...action.payload.encodingOptions };
         draft.dirty = false;
         break;
 
@@ -150,13 +163,33 @@ export function appReducer(state: AppState, action: AppAction): AppState {
               const isOgg = ext === 'OGG';
               return (isOgg && f.is_vorbis) || !isOgg;
             })
-            .map(f => f.name);
+            .map(f => f.relative_path);
         }
         break;
 
       case 'DESELECT_ALL_SCAN_FILES':
         draft.selectedScanFiles = [];
         break;
+
+      case 'TOGGLE_RULE': {
+        const idx = draft.disabledRules.indexOf(action.payload);
+        if (idx === -1) {
+          draft.disabledRules.push(action.payload);
+        } else {
+          draft.disabledRules.splice(idx, 1);
+        }
+        draft.dirty = true;
+        break;
+      }
+
+      case 'SET_ENCODING_OPTIONS':
+        draft.encodingOptions = action.payload;
+        draft.dirty = true;
+        break;
+
+      case 'RESTORE_ENCODING_OPTIONS':
+        draft.encodingOptions = { ...defaultEncodingOptions, ...action.payload };
+        break;
     }
   });
 }
@@ -35,6 +35,7 @@ export interface OriginalAudio {
 export interface AudioFileInfo {
   name: string;
   path: string;
+  relative_path: string;
   size_bytes: number;
   size_display: string;
   is_valid_ogg: boolean;
@@ -43,6 +44,91 @@ export interface AudioFileInfo {
   duration_secs: number | null;
   sample_rate: number | null;
   channels: number | null;
+  format: string;
+  detected_format: string | null;
+  codec: string | null;
+  bits_per_sample: number | null;
+  title: string | null;
+  artist: string | null;
+  album: string | null;
+  vendor: string | null;
+  tags: Record<string, string[]>;
+  loop_points: LoopPoints | null;
+  total_samples: number | null;
+  decode_check: DecodeCheck | null;
+  loudness: LoudnessStats | null;
+  silence: SilenceStats | null;
+  content_hash: string | null;
+  audio_fingerprint: string | null;
+  violations: RuleViolation[];
+}
+
+export type RuleSeverity = 'error' | 'warning';
+
+export interface RuleViolation {
+  rule: string;
+  severity: RuleSeverity;
+  message: string;
+}
+
+export interface RuleInfo {
+  id: string;
+  severity: RuleSeverity;
+  description: string;
+}
+
+export interface LoudnessStats {
+  integrated_lufs: number | null;
+  loudness_range_lu: number | null;
+  true_peak_dbtp: number | null;
+  sample_peak_dbfs: number | null;
+  clipped_samples: number;
+}
+
+export interface DecodeCheck {
+  decoded_samples: number;
+  error_count: number;
+  errors: string[];
+  length_matches: boolean | null;
+}
+
+export interface SilenceStats {
+  threshold_db: number;
+  leading_secs: number;
+  trailing_secs: number;
+}
+
+export interface ScanOptions {
+  deep_validation?: boolean;
+  decode_test?: boolean;
+  loudness?: boolean;
+  silence?: boolean;
+  silence_threshold_db?: number;
+  refresh?: boolean;
+  verify_hash?: boolean;
+  find_duplicates?: boolean;
+  audio_fingerprint?: boolean;
+  include?: string[];
+  exclude?: string[];
+  max_depth?: number;
+  include_hidden?: boolean;
+  symlinks?: SymlinkPolicy;
+  disabled_rules?: string[];
+}
+
+export type SymlinkPolicy = 'follow' | 'files_only' | 'skip';
+
+export interface LoopPoints {
+  start_sample: number;
+  end_sample: number | null;
+  start_secs: number;
+  end_secs: number | null;
+}
+
+export interface AudioEntryWarning {
+  audio_id: string;
+  file: string;
+  message: string;
 }
 
 export interface ScanResult {
@@ -51,6 +137,49 @@ export interface ScanResult {
   total_valid: number;
   total_invalid: number;
   total_size: string;
+  cancelled: boolean;
+  duplicates: DuplicateGroup[];
+  warnings: string[];
+  summary: ScanSummary;
+}
+
+export interface FileSize {
+  relative_path: string;
+  size_bytes: number;
+}
+
+export interface FolderSummary {
+  folder: string;
+  files: number;
+  valid: number;
+  total_bytes: number;
+  total_duration_secs: number;
+}
+
+export interface ScanSummary {
+  total_files: number;
+  total_valid: number;
+  total_bytes: number;
+  total_duration_secs: number;
+  average_duration_secs: number | null;
+  by_codec: Record<string, number>;
+  by_sample_rate: Record<string, number>;
+  by_channels: Record<string, number>;
+  largest_files: FileSize[];
+  folders: FolderSummary[];
+}
+
+export interface DuplicateGroup {
+  kind: 'content' | 'audio';
+  hash: string;
+  files: string[];
+  wasted_bytes: number;
+}
+
+export interface ScanProgress {
+  done: number;
+  total: number;
+  current_file: string;
 }
 
 export interface ExportResult {
@@ -77,15 +206,131 @@ export interface ProjectData {
   }[];
   version: string;
   saved_at: string;
+  disabled_rules?: string[];
+  encoding_options?: EncodingOptions;
+}
+
+export type ChannelPolicy = 'keep' | 'mono' | 'stereo';
+
+export interface EncodingOptions {
+  quality: number;
+  sample_rate: number | null;
+  channels: ChannelPolicy;
+  max_bitrate_kbps: number | null;
+  normalize: LoudnessTarget | null;
+  edit: EditOptions;
+}
+
+export type Trim =
+  | { mode: 'none' }
+  | { mode: 'time'; start_secs: number; end_secs: number | null }
+  | { mode: 'silence'; threshold_db: number };
+
+export interface EditOptions {
+  trim: Trim;
+  fade_in_ms: number;
+  fade_out_ms: number;
+  loop_seam_ms: number;
+}
+
+export interface EditReport {
+  start_secs: number;
+  end_secs: number;
+  seam_secs: number;
+  used_loop_tags: boolean;
+}
+
+export interface LoudnessTarget {
+  target_lufs: number;
+  true_peak_dbtp: number;
+}
+
+export interface EncodingPreset {
+  id: string;
+  name: string;
+  description: string;
+  options: EncodingOptions;
+}
+
+export interface ConvertResult {
+  success: boolean;
+  output_path: string;
+  message: string;
+  encoder: 'native' | 'ffmpeg' | null;
+  loudness: LoudnessReport | null;
+  edit: EditReport | null;
+  skipped: boolean;
+  backup_path: string | null;
+  output_info: AudioFileInfo | null;
+  error?: string;
+}
+
+export type ToolSource = 'configured' | 'path' | 'common_dir' | 'sidecar';
+
+export interface ToolInfo {
+  path: string;
+  version: string;
+  source: ToolSource;
+}
+
+export interface ToolchainStatus {
+  ffmpeg: ToolInfo | null;
+  ffprobe: ToolInfo | null;
+  libvorbis: boolean;
+  configured_path: string | null;
+}
+
+export type CollisionPolicy = 'suffix' | 'overwrite' | 'skip';
+
+export interface OutputOptions {
+  collision: CollisionPolicy;
+  backup_dir: string | null;
+}
+
+export interface LoudnessReport {
+  target_lufs: number;
+  true_peak_ceiling_dbtp: number;
+  before: LoudnessStats;
+  after: LoudnessStats;
+  gain_db: number;
+}
+
+export type ConvertJobStatus = 'queued' | 'converting' | 'done' | 'skipped' | 'failed' | 'cancelled';
+
+export interface ConvertProgress {
+  job_id: string;
+  status: ConvertJobStatus;
+  progress: number;
+  error: string | null;
+}
+
+export interface ConvertJobOutcome {
+  id: string;
+  source_path: string;
+  status: ConvertJobStatus;
+  result: ConvertResult | null;
+  error: string | null;
+}
+
+export interface BatchConvertResult {
+  jobs: ConvertJobOutcome[];
+  succeeded: number;
+  skipped: number;
+  failed: number;
+  cancelled: number;
 }
 
 export interface ConvertJob {
   id: string;
   sourceFile: string;
   targetFormat: 'ogg' | 'wav';
-  status: 'pending' | 'converting' | 'done' | 'error';
+  status: 'pending' | 'converting' | 'done' | 'skipped' | 'error' | 'cancelled';
   progress: number;
   outputPath?: string;
+  backupPath?: string | null;
+  outputInfo?: AudioFileInfo | null;
+  loudness?: LoudnessReport | null;
+  edit?: EditReport | null;
   error?: string;
 }
 
@@ -111,6 +356,8 @@ export interface AppState {
   player: PlayerState;
   convertJobs: ConvertJob[];
   selectedScanFiles: string[];
+  disabledRules: string[];
+  encodingOptions: EncodingOptions;
 }
 
 export type AppAction =
@@ -126,7 +373,7 @@ export type AppAction =
   | { type: 'SET_LOADING'; payload: { loading: boolean; message?: string } }
   | { type: 'SET_SCAN_RESULT'; payload: ScanResult | null }
   | { type: 'SET_WATCHING'; payload: boolean }
-  | { type: 'LOAD_PROJECT'; payload: { config: ModConfig; audios: AudioEntry[] } }
+  | { type: 'LOAD_PROJECT'; payload: { config: ModConfig; audios: AudioEntry[]; disabledRules?: string[]; encodingOptions?: EncodingOptions } }
   | { type: 'RESET' }
   | { type: 'MARK_SAVED' }
   | { type: 'SET_PLAYER'; payload: Partial<PlayerState> }
@@ -136,4 +383,7 @@ export type AppAction =
   | { type: 'CLEAR_CONVERT_JOBS' }
   | { type: 'TOGGLE_SCAN_FILE'; payload: string }
   | { type: 'SELECT_ALL_SCAN_FILES' }
-  | { type: 'DESELECT_ALL_SCAN_FILES' };
+  | { type: 'DESELECT_ALL_SCAN_FILES' }
+  | { type: 'TOGGLE_RULE'; payload: string }
+  | { type: 'SET_ENCODING_OPTIONS'; payload: EncodingOptions }
+  | { type: 'RESTORE_ENCODING_OPTIONS'; payload: Partial<EncodingOptions> };